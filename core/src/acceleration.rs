@@ -0,0 +1,153 @@
+use crate::calculations::{calculate_cm, convert_sensitivity, effective_dpi};
+use crate::models::AccelCurve;
+
+pub fn accel_multiplier(curve: &AccelCurve, speed: f64) -> f64 {
+    let capped = |multiplier: f64, cap: &Option<f64>| match cap {
+        Some(cap) if *cap > 0.0 => multiplier.min(*cap),
+        _ => multiplier,
+    };
+
+    match curve {
+        AccelCurve::Linear { accel, offset, cap } => {
+            capped(1.0 + accel * (speed - offset).max(0.0), cap)
+        }
+        AccelCurve::Classic { accel, exponent, offset, cap } => {
+            capped(1.0 + (accel * (speed - offset).max(0.0)).powf(exponent - 1.0), cap)
+        }
+        AccelCurve::Power { scale, exponent, output_offset, cap } => {
+            capped(output_offset + (scale * speed).powf(*exponent), cap)
+        }
+        AccelCurve::Natural { decay, limit, offset } => {
+            1.0 + (limit - 1.0) * (1.0 - (-decay * (speed - offset).max(0.0)).exp())
+        }
+    }
+}
+
+// Curves work on counts/ms, so a new DPI stretches the speed axis. Rescaling the parameters by the
+// DPI ratio keeps the multiplier the same at the same physical hand speed.
+pub fn rescale_curve(curve: &AccelCurve, old_dpi: i32, new_dpi: i32) -> AccelCurve {
+    let ratio = new_dpi as f64 / old_dpi as f64;
+
+    match curve.clone() {
+        AccelCurve::Linear { accel, offset, cap } => AccelCurve::Linear {
+            accel: accel / ratio,
+            offset: offset * ratio,
+            cap,
+        },
+        AccelCurve::Classic { accel, exponent, offset, cap } => AccelCurve::Classic {
+            accel: accel / ratio,
+            exponent,
+            offset: offset * ratio,
+            cap,
+        },
+        AccelCurve::Power { scale, exponent, output_offset, cap } => AccelCurve::Power {
+            scale: scale / ratio,
+            exponent,
+            output_offset,
+            cap,
+        },
+        AccelCurve::Natural { decay, limit, offset } => AccelCurve::Natural {
+            decay: decay / ratio,
+            limit,
+            offset: offset * ratio,
+        },
+    }
+}
+
+// cm/360 at evenly spaced hand speeds from 0 to max_speed in cm/s
+pub fn curve_samples(
+    curve: &AccelCurve,
+    sens: f64,
+    dpi: i32,
+    yaw: f64,
+    dpi_correction: f64,
+    max_speed: f64,
+    points: usize,
+) -> Vec<(f64, f64)> {
+    let points = points.max(2);
+
+    (0..points)
+        .map(|i| {
+            let speed = max_speed * i as f64 / (points - 1) as f64;
+            let counts_per_ms = speed / 2.54 * effective_dpi(dpi, dpi_correction) / 1000.0;
+            let multiplier = accel_multiplier(curve, counts_per_ms);
+            (speed, calculate_cm(sens * multiplier, dpi, yaw, dpi_correction))
+        })
+        .collect()
+}
+
+// Converts the base sensitivity and the curve, so every hand speed gives the same cm/360 as before
+pub fn convert_accel_curve(
+    curve: &AccelCurve,
+    sens: f64,
+    dpi: i32,
+    yaw1: f64,
+    new_dpi: i32,
+    yaw2: f64,
+    dpi_correction: f64,
+) -> (f64, AccelCurve) {
+    let new_sens = convert_sensitivity(sens, dpi, new_dpi, yaw1, yaw2, dpi_correction);
+    (new_sens, rescale_curve(curve, dpi, new_dpi))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn curves() -> Vec<AccelCurve> {
+        vec![
+            AccelCurve::Linear { accel: 0.05, offset: 2.0, cap: Some(3.0) },
+            AccelCurve::Classic { accel: 0.1, exponent: 2.5, offset: 1.0, cap: None },
+            AccelCurve::Power { scale: 0.3, exponent: 0.5, output_offset: 1.0, cap: Some(4.0) },
+            AccelCurve::Natural { decay: 0.2, limit: 2.5, offset: 1.5 },
+        ]
+    }
+
+    #[test]
+    fn multiplier_is_one_below_the_offset() {
+        for curve in curves().iter().filter(|curve| !matches!(curve, AccelCurve::Power { .. })) {
+            assert_eq!(accel_multiplier(curve, 0.5), 1.0);
+        }
+    }
+
+    #[test]
+    fn cap_limits_the_multiplier() {
+        let curve = AccelCurve::Linear { accel: 0.05, offset: 2.0, cap: Some(3.0) };
+
+        assert!((accel_multiplier(&curve, 12.0) - 1.5).abs() < 1e-12);
+        assert_eq!(accel_multiplier(&curve, 1000.0), 3.0);
+    }
+
+    #[test]
+    fn natural_curve_approaches_the_limit() {
+        let curve = AccelCurve::Natural { decay: 0.2, limit: 2.5, offset: 0.0 };
+
+        assert!(accel_multiplier(&curve, 5.0) < accel_multiplier(&curve, 10.0));
+        assert!((accel_multiplier(&curve, 1000.0) - 2.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn conversion_keeps_cm_per360_at_every_hand_speed() {
+        for curve in curves() {
+            let before = curve_samples(&curve, 1.5, 800, 0.022, 1.0, 200.0, 21);
+            let (sens, converted) = convert_accel_curve(&curve, 1.5, 800, 0.022, 1600, 0.0066, 1.0);
+            let after = curve_samples(&converted, sens, 1600, 0.0066, 1.0, 200.0, 21);
+
+            for ((speed, cm_before), (_, cm_after)) in before.iter().zip(&after) {
+                assert!((cm_before - cm_after).abs() < 1e-9, "{} cm/s: {} vs {}", speed, cm_before, cm_after);
+            }
+        }
+    }
+
+    #[test]
+    fn samples_span_the_speed_range() {
+        let curve = AccelCurve::Linear { accel: 0.0, offset: 0.0, cap: None };
+        let samples = curve_samples(&curve, 1.0, 800, 0.022, 1.0, 100.0, 1);
+
+        assert_eq!(samples.len(), 2);
+        assert_eq!(samples[0].0, 0.0);
+        assert_eq!(samples[1].0, 100.0);
+        assert!((samples[0].1 - calculate_cm(1.0, 800, 0.022, 1.0)).abs() < 1e-12);
+        assert!((samples[0].1 - samples[1].1).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,121 @@
+use crate::calculations::source_cm_per360;
+use crate::models::{GameSensResult, GameYaw, TableFormat};
+use crate::optimizer::game_sens_result;
+
+// Games whose yaw gives no valid sensitivity, like a yaw of 0 still being measured, are left out
+pub fn convert_to_all_games(
+    source: &GameYaw,
+    sens: f64,
+    dpi: i32,
+    new_dpi: i32,
+    games: &[GameYaw],
+    dpi_correction: f64,
+    pointer_speed: u32,
+) -> Result<Vec<GameSensResult>, String> {
+    let cm_per360 = source_cm_per360(sens, &source.unit(), dpi, dpi_correction, pointer_speed)?;
+    if new_dpi <= 0 {
+        return Err("DPI must be greater than zero".to_string());
+    }
+
+    Ok(games
+        .iter()
+        .map(|game| game_sens_result(game, cm_per360, new_dpi, dpi_correction, pointer_speed))
+        .filter(|result| result.sens.is_finite() && result.sens > 0.0 && result.cm_per360.is_finite())
+        .collect())
+}
+
+pub fn format_table(results: &[GameSensResult], format: TableFormat) -> String {
+    let mut table = String::new();
+
+    match format {
+        TableFormat::Csv => {
+            table.push_str("game,sens,settable_sens,cm_per360,error_percent\n");
+            for result in results {
+                table.push_str(&format!(
+                    "{},{:.6},{},{:.3},{:.3}\n",
+                    csv_field(&result.name),
+                    result.sens,
+                    result.settable_sens,
+                    result.cm_per360,
+                    result.error_percent
+                ));
+            }
+        }
+        TableFormat::Markdown => {
+            table.push_str("| Game | Sensitivity | Settable | cm/360 | Error |\n");
+            table.push_str("|---|---:|---:|---:|---:|\n");
+            for result in results {
+                table.push_str(&format!(
+                    "| {} | {:.6} | {} | {:.3} | {:.3}% |\n",
+                    result.name.replace('|', "\\|"),
+                    result.sens,
+                    result.settable_sens,
+                    result.cm_per360,
+                    result.error_percent
+                ));
+            }
+        }
+    }
+
+    table
+}
+
+pub(crate) fn csv_field(value: &str) -> String {
+    if value.contains(',') || value.contains('"') || value.contains('\n') {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ScopeConvention, SensGranularity};
+
+    fn game(name: &str, yaw: f64) -> GameYaw {
+        GameYaw {
+            name: name.to_string(),
+            yaw,
+            yaw_curve: None,
+            raw_input: true,
+            granularity: SensGranularity::default(),
+            scopes: Vec::new(),
+            scope_convention: ScopeConvention::default(),
+            gamepad: None,
+            config: None,
+            steam_app_id: None,
+        }
+    }
+
+    #[test]
+    fn converts_to_every_game() {
+        let games = [game("CS2", 0.022), game("Valorant", 0.07)];
+        let results = convert_to_all_games(&games[0], 2.0, 800, 1600, &games, 1.0, 10).unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert!((results[0].sens - 1.0).abs() < 1e-9);
+        assert!((results[1].sens - 0.022 / 0.07).abs() < 1e-9);
+        assert!((results[1].cm_per360 - results[0].cm_per360).abs() < 1e-9);
+    }
+
+    #[test]
+    fn rejects_an_invalid_source() {
+        let games = [game("CS2", 0.022), game("New", 0.0)];
+
+        assert!(convert_to_all_games(&games[0], 0.0, 800, 800, &games, 1.0, 10).is_err());
+        assert!(convert_to_all_games(&games[0], f64::NAN, 800, 800, &games, 1.0, 10).is_err());
+        assert!(convert_to_all_games(&games[0], 1.0, 0, 800, &games, 1.0, 10).is_err());
+        assert!(convert_to_all_games(&games[0], 1.0, 800, 0, &games, 1.0, 10).is_err());
+        assert!(convert_to_all_games(&games[1], 1.0, 800, 800, &games, 1.0, 10).is_err());
+    }
+
+    #[test]
+    fn leaves_out_games_without_yaw() {
+        let games = [game("CS2", 0.022), game("New", 0.0), game("Broken", f64::NAN)];
+        let results = convert_to_all_games(&games[0], 1.0, 800, 800, &games, 1.0, 10).unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "CS2");
+    }
+}
@@ -0,0 +1,610 @@
+use crate::models::{AngularResolution, FovScale, SensConversion, SensGranularity, SensUnit};
+
+// DPI values tried when looking for a DPI that the game's sensitivity steps can match more closely
+const SUGGESTED_DPI_MIN: i32 = 200;
+const SUGGESTED_DPI_MAX: i32 = 3200;
+const SUGGESTED_DPI_STEP: usize = 50;
+
+// Windows pointer speed multipliers for the registry values 1-20 with "Enhance pointer precision" off.
+// The slider in the mouse settings has 11 notches, the default 6/11 is value 10.
+const POINTER_SPEED_MULTIPLIERS: [f64; 20] = [
+    0.03125, 0.0625, 0.125, 0.25, 0.375, 0.5, 0.625, 0.75, 0.875, 1.0,
+    1.25, 1.5, 1.75, 2.0, 2.25, 2.5, 2.75, 3.0, 3.25, 3.5,
+];
+
+pub fn calculate_scoped_counts(cm_per360: f64, dpi: i32, dpi_correction: f64, normal_fov: f64, scoped_fov: f64) -> i32 {
+    let normal_fov_radians = std::f64::consts::PI * normal_fov / 180.0;
+    let scoped_fov_radians = std::f64::consts::PI * scoped_fov / 180.0;
+
+    // Calculate the number of counts needed for a 360-degree turn
+    let inches_per360 = cm_per360 / 2.54;
+    let counts_per360 = inches_per360 * effective_dpi(dpi, dpi_correction);
+
+    // Apply focal length scaling
+    let scoped_counts = counts_per360 * ((normal_fov_radians / 2.0).tan() / (scoped_fov_radians / 2.0).tan());
+    scoped_counts.round() as i32
+}
+
+// cm/360 of a turn that was measured with the tracker
+pub fn calculate_measured_cm(counts: i32, dpi: i32, dpi_correction: f64) -> f64 {
+    2.54 * counts.abs() as f64 / effective_dpi(dpi, dpi_correction)
+}
+
+// FOV of a scope, measured by moving the crosshair from the center to the edge of the hipfire view
+pub fn calculate_measured_fov(cm_per360: f64, dpi: i32, dpi_correction: f64, game_sens: f64, counts: i32) -> f64 {
+    let inches_per_360 = cm_per360 / 2.54;
+    let counts_per360 = inches_per_360 * effective_dpi(dpi, dpi_correction);
+
+    estimate_fov(game_sens, calculate_yaw(counts_per360 as i32, game_sens), counts.abs())
+}
+
+pub fn calculate_yaw(counts: i32, sens: f64) -> f64 {
+    let inc = 360.0 / counts as f64;
+    inc / sens
+}
+
+pub fn estimate_fov(sens: f64, yaw: f64, counts: i32) -> f64 {
+    (yaw * counts as f64 * sens) * 2.0
+}
+
+pub fn calculate_counts(cm: f64, dpi: i32, dpi_correction: f64) -> i32 {
+    let inches_per360 = cm / 2.54;
+    let counts_per360 = inches_per360 * effective_dpi(dpi, dpi_correction);
+    counts_per360.round() as i32
+}
+
+pub fn calculate_cm(sens: f64, dpi: i32, yaw: f64, dpi_correction: f64) -> f64 {
+    let counts = 360.0 / (sens * yaw);
+    let inches_per360 = counts / effective_dpi(dpi, dpi_correction);
+    inches_per360 * 2.54
+}
+
+pub fn calculate_sens(cm_per360: f64, dpi: i32, yaw: f64, dpi_correction: f64) -> f64 {
+    let inches_per360 = cm_per360 / 2.54;
+    let counts = inches_per360 * effective_dpi(dpi, dpi_correction);
+    360.0 / (counts * yaw)
+}
+
+pub fn convert_sensitivity(old_sens: f64, old_dpi: i32, new_dpi: i32, yaw1: f64, yaw2: f64, dpi_correction: f64) -> f64 {
+    let cm_per360 = calculate_cm(old_sens, old_dpi, yaw1, dpi_correction);
+    calculate_sens(cm_per360, new_dpi, yaw2, dpi_correction)
+}
+
+pub fn pointer_speed_multiplier(pointer_speed: u32) -> f64 {
+    let index = pointer_speed.clamp(1, POINTER_SPEED_MULTIPLIERS.len() as u32) - 1;
+    POINTER_SPEED_MULTIPLIERS[index as usize]
+}
+
+// Games that read the cursor instead of raw input turn by the pointer-speed scaled counts
+pub fn effective_yaw(yaw: f64, raw_input: bool, pointer_speed: u32) -> f64 {
+    if raw_input {
+        yaw
+    } else {
+        yaw * pointer_speed_multiplier(pointer_speed)
+    }
+}
+
+// The DPI the sensor really reports, after applying the correction from the DPI test
+pub fn effective_dpi(dpi: i32, dpi_correction: f64) -> f64 {
+    dpi as f64 * dpi_correction
+}
+
+// Returns the mean effective DPI over all ruler passes and the standard deviation between them
+pub fn calculate_effective_dpi(samples: &[i32], distance_cm: f64) -> (f64, f64) {
+    if samples.is_empty() || distance_cm <= 0.0 {
+        return (0.0, 0.0);
+    }
+
+    let inches = distance_cm / 2.54;
+    let dpis: Vec<f64> = samples.iter().map(|&counts| counts as f64 / inches).collect();
+
+    let mean = dpis.iter().sum::<f64>() / dpis.len() as f64;
+    let variance = dpis.iter().map(|dpi| (dpi - mean).powi(2)).sum::<f64>() / dpis.len() as f64;
+    (mean, variance.sqrt())
+}
+// Rounds to the closest value the game accepts and clamps it to the game's range
+pub fn nearest_settable_sens(sens: f64, granularity: &SensGranularity) -> f64 {
+    let mut settable = sens;
+
+    if let Some(step) = granularity.sens_step.filter(|step| *step > 0.0) {
+        settable = (sens / step).round() * step;
+        // Get rid of float noise like 0.30000000000000004 so the value matches what the game shows
+        let factor = 10f64.powi(step_decimals(step));
+        settable = (settable * factor).round() / factor;
+
+        if settable <= 0.0 {
+            settable = step;
+        }
+    }
+
+    if let Some(min) = granularity.sens_min {
+        settable = settable.max(min);
+    }
+    if let Some(max) = granularity.sens_max {
+        settable = settable.min(max);
+    }
+
+    settable
+}
+
+// Decimals of the step as written, e.g. 2 for 0.25 and 3 for 0.125
+fn step_decimals(step: f64) -> i32 {
+    step.to_string()
+        .split_once('.')
+        .map_or(0, |(_, fraction)| fraction.len() as i32)
+}
+
+pub fn calculate_settable_sens(
+    cm_per360: f64,
+    dpi: i32,
+    yaw: f64,
+    dpi_correction: f64,
+    granularity: &SensGranularity,
+) -> SensConversion {
+    let sens = calculate_sens(cm_per360, dpi, yaw, dpi_correction);
+    let settable_sens = nearest_settable_sens(sens, granularity);
+    let actual_cm = calculate_cm(settable_sens, dpi, yaw, dpi_correction);
+    let error_percent = (actual_cm - cm_per360) / cm_per360 * 100.0;
+
+    let suggested_dpi = suggest_dpi(cm_per360, yaw, dpi_correction, granularity)
+        // Only worth suggesting when it is noticeably better, not just float noise on an exact match
+        .filter(|&(_, error)| error.abs() + 0.01 < error_percent.abs())
+        .map(|(dpi, _)| dpi);
+
+    SensConversion {
+        sens,
+        settable_sens,
+        cm_per360: actual_cm,
+        error_percent,
+        suggested_dpi,
+    }
+}
+
+// Returns the DPI with the smallest cm/360 error after rounding the sensitivity, and that error in percent
+pub fn suggest_dpi(cm_per360: f64, yaw: f64, dpi_correction: f64, granularity: &SensGranularity) -> Option<(i32, f64)> {
+    (SUGGESTED_DPI_MIN..=SUGGESTED_DPI_MAX)
+        .step_by(SUGGESTED_DPI_STEP)
+        .map(|dpi| {
+            let sens = nearest_settable_sens(calculate_sens(cm_per360, dpi, yaw, dpi_correction), granularity);
+            let error = (calculate_cm(sens, dpi, yaw, dpi_correction) - cm_per360) / cm_per360 * 100.0;
+            (dpi, error)
+        })
+        .filter(|(_, error)| error.is_finite())
+        .min_by(|a, b| a.1.abs().total_cmp(&b.1.abs()))
+}
+
+// Converts a FOV from any scale to the horizontal FOV of a screen with the given aspect ratio
+pub fn fov_to_horizontal(fov: f64, scale: FovScale, width: f64, height: f64) -> f64 {
+    let aspect = height / width;
+    let half_fov = (fov / 2.0).to_radians();
+
+    let half_horizontal = match scale {
+        FovScale::Horizontal => half_fov,
+        FovScale::Hor4ML3 => ((3.0 / 4.0) / aspect * half_fov.tan()).atan(),
+        FovScale::Vertical => (half_fov.tan() / aspect).atan(),
+    };
+
+    (half_horizontal * 2.0).to_degrees()
+}
+
+pub fn horizontal_to_fov(horizontal_fov: f64, scale: FovScale, width: f64, height: f64) -> f64 {
+    let aspect = height / width;
+    let half_horizontal = (horizontal_fov / 2.0).to_radians();
+
+    let half_fov = match scale {
+        FovScale::Horizontal => half_horizontal,
+        FovScale::Hor4ML3 => (aspect / (3.0 / 4.0) * half_horizontal.tan()).atan(),
+        FovScale::Vertical => (half_horizontal.tan() * aspect).atan(),
+    };
+
+    (half_fov * 2.0).to_degrees()
+}
+
+// How far a single count moves the crosshair, measured in pixels at the center of the screen
+pub fn calculate_angular_resolution(
+    sens: f64,
+    yaw: f64,
+    fov: f64,
+    scale: FovScale,
+    width: f64,
+    height: f64,
+) -> AngularResolution {
+    let deg_per_count = sens * yaw;
+    let horizontal_fov = fov_to_horizontal(fov, scale, width, height);
+
+    // With a rectilinear projection the screen is most stretched at the center, so this is the worst case
+    let pixels_per_degree = (width / 2.0) / (horizontal_fov / 2.0).to_radians().tan() * 1f64.to_radians();
+    let pixels_per_count = deg_per_count * pixels_per_degree;
+
+    AngularResolution {
+        deg_per_count,
+        horizontal_fov,
+        pixels_per_degree,
+        pixels_per_count,
+        counts_per_pixel: if pixels_per_count > 0.0 { 1.0 / pixels_per_count } else { 0.0 },
+        skips_pixels: pixels_per_count > 1.0,
+    }
+}
+
+// Linear interpolation between measured (sens, yaw) points, holding the outermost yaw beyond them
+pub fn curve_yaw(curve: &[(f64, f64)], sens: f64) -> f64 {
+    let mut points = curve.to_vec();
+    points.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    match points.iter().position(|&(s, _)| s >= sens) {
+        None => points.last().map_or(0.0, |p| p.1),
+        Some(0) => points[0].1,
+        Some(i) => {
+            let (s0, y0) = points[i - 1];
+            let (s1, y1) = points[i];
+            if s1 == s0 {
+                y1
+            } else {
+                y0 + (y1 - y0) * (sens - s0) / (s1 - s0)
+            }
+        }
+    }
+}
+
+pub fn game_deg_per_count(yaw: f64, yaw_curve: &Option<Vec<(f64, f64)>>, raw_input: bool, pointer_speed: u32, sens: f64) -> f64 {
+    let yaw = match yaw_curve {
+        Some(curve) if !curve.is_empty() => curve_yaw(curve, sens),
+        _ => yaw,
+    };
+    sens * effective_yaw(yaw, raw_input, pointer_speed)
+}
+
+// Inverse of game_deg_per_count. A curve has no closed form, so the sensitivity is found by bisection.
+pub fn game_sens_for_deg_per_count(
+    yaw: f64,
+    yaw_curve: &Option<Vec<(f64, f64)>>,
+    raw_input: bool,
+    pointer_speed: u32,
+    deg_per_count: f64,
+) -> f64 {
+    match yaw_curve {
+        Some(curve) if !curve.is_empty() => {
+            let turn = |sens: f64| game_deg_per_count(yaw, yaw_curve, raw_input, pointer_speed, sens);
+
+            let mut low = 0.0;
+            let mut high = 1.0;
+            while turn(high) < deg_per_count && high < 1e9 {
+                high *= 2.0;
+            }
+            for _ in 0..100 {
+                let mid = (low + high) / 2.0;
+                if turn(mid) < deg_per_count {
+                    low = mid;
+                } else {
+                    high = mid;
+                }
+            }
+            (low + high) / 2.0
+        }
+        _ => deg_per_count / effective_yaw(yaw, raw_input, pointer_speed),
+    }
+}
+
+pub fn unit_to_cm_per360(unit: &SensUnit, value: f64, dpi: i32, dpi_correction: f64, pointer_speed: u32) -> f64 {
+    let dpi = effective_dpi(dpi, dpi_correction);
+
+    match unit {
+        SensUnit::Game { yaw, yaw_curve, raw_input, .. } => {
+            let deg_per_count = game_deg_per_count(*yaw, yaw_curve, *raw_input, pointer_speed, value);
+            360.0 / deg_per_count / dpi * 2.54
+        }
+        SensUnit::Cm360 => value,
+        SensUnit::InchesPer360 => value * 2.54,
+        SensUnit::DegPerCount => 360.0 / value / dpi * 2.54,
+        SensUnit::CountsPer360 => value / dpi * 2.54,
+        SensUnit::DegPerMm => 360.0 / value / 10.0,
+    }
+}
+
+pub fn cm_per360_to_unit(unit: &SensUnit, cm_per360: f64, dpi: i32, dpi_correction: f64, pointer_speed: u32) -> f64 {
+    let counts_per360 = cm_per360 / 2.54 * effective_dpi(dpi, dpi_correction);
+
+    match unit {
+        SensUnit::Game { yaw, yaw_curve, raw_input, .. } => {
+            game_sens_for_deg_per_count(*yaw, yaw_curve, *raw_input, pointer_speed, 360.0 / counts_per360)
+        }
+        SensUnit::Cm360 => cm_per360,
+        SensUnit::InchesPer360 => cm_per360 / 2.54,
+        SensUnit::DegPerCount => 360.0 / counts_per360,
+        SensUnit::CountsPer360 => counts_per360,
+        SensUnit::DegPerMm => 360.0 / (cm_per360 * 10.0),
+    }
+}
+
+// Units that are counted in mouse counts and so need the DPI to become a distance
+fn uses_dpi(unit: &SensUnit) -> bool {
+    matches!(unit, SensUnit::Game { .. } | SensUnit::DegPerCount | SensUnit::CountsPer360)
+}
+
+// cm/360 of the sensitivity a conversion starts from, checked the same way for every conversion
+pub fn source_cm_per360(
+    value: f64,
+    from: &SensUnit,
+    dpi: i32,
+    dpi_correction: f64,
+    pointer_speed: u32,
+) -> Result<f64, String> {
+    // An empty input field arrives as 0, which has no cm/360
+    if !value.is_finite() || value <= 0.0 {
+        return Err("Sensitivity must be greater than zero".to_string());
+    }
+    if uses_dpi(from) && dpi <= 0 {
+        return Err("DPI must be greater than zero".to_string());
+    }
+
+    let cm_per360 = unit_to_cm_per360(from, value, dpi, dpi_correction, pointer_speed);
+    if !cm_per360.is_finite() || cm_per360 <= 0.0 {
+        return Err("The sensitivity has no valid cm/360, check the yaw of the game".to_string());
+    }
+
+    Ok(cm_per360)
+}
+
+pub fn convert_unit(
+    value: f64,
+    from: &SensUnit,
+    dpi: i32,
+    to: &SensUnit,
+    new_dpi: i32,
+    dpi_correction: f64,
+    pointer_speed: u32,
+) -> Result<SensConversion, String> {
+    let cm_per360 = source_cm_per360(value, from, dpi, dpi_correction, pointer_speed)?;
+    // Only the sides that count in mouse counts need a DPI, the converter leaves it empty otherwise
+    if uses_dpi(to) && new_dpi <= 0 {
+        return Err("DPI must be greater than zero".to_string());
+    }
+    if let SensUnit::Game { yaw, yaw_curve: None, raw_input, .. } = to {
+        let yaw = effective_yaw(*yaw, *raw_input, pointer_speed);
+        if !yaw.is_finite() || yaw <= 0.0 {
+            return Err("The yaw of the target game must be greater than zero".to_string());
+        }
+    }
+
+    let conversion = match to {
+        SensUnit::Game { yaw, yaw_curve: None, raw_input, granularity } => calculate_settable_sens(
+            cm_per360,
+            new_dpi,
+            effective_yaw(*yaw, *raw_input, pointer_speed),
+            dpi_correction,
+            granularity,
+        ),
+        SensUnit::Game { granularity, .. } => {
+            let sens = cm_per360_to_unit(to, cm_per360, new_dpi, dpi_correction, pointer_speed);
+            let settable_sens = nearest_settable_sens(sens, granularity);
+            let actual_cm = unit_to_cm_per360(to, settable_sens, new_dpi, dpi_correction, pointer_speed);
+
+            SensConversion {
+                sens,
+                settable_sens,
+                cm_per360: actual_cm,
+                error_percent: (actual_cm - cm_per360) / cm_per360 * 100.0,
+                suggested_dpi: None,
+            }
+        }
+        _ => {
+            let converted = cm_per360_to_unit(to, cm_per360, new_dpi, dpi_correction, pointer_speed);
+
+            SensConversion {
+                sens: converted,
+                settable_sens: converted,
+                cm_per360,
+                error_percent: 0.0,
+                suggested_dpi: None,
+            }
+        }
+    };
+
+    if !conversion.sens.is_finite() || conversion.sens <= 0.0 {
+        return Err("The sensitivity has no valid value in the target unit, check the yaw of the game".to_string());
+    }
+
+    Ok(conversion)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(yaw: f64, sens_step: Option<f64>) -> SensUnit {
+        SensUnit::Game {
+            yaw,
+            yaw_curve: None,
+            raw_input: true,
+            granularity: SensGranularity {
+                sens_step,
+                ..SensGranularity::default()
+            },
+        }
+    }
+
+    #[test]
+    fn cm_and_sens_are_inverse() {
+        let cm_per360 = calculate_cm(1.5, 800, 0.022, 1.0);
+
+        assert!((cm_per360 - 34.636363).abs() < 1e-4);
+        assert!((calculate_sens(cm_per360, 800, 0.022, 1.0) - 1.5).abs() < 1e-9);
+        assert!((convert_sensitivity(1.0, 800, 1600, 0.022, 0.022, 1.0) - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn every_unit_converts_back_to_the_same_cm_per360() {
+        let units = [
+            game(0.022, None),
+            SensUnit::Cm360,
+            SensUnit::InchesPer360,
+            SensUnit::DegPerCount,
+            SensUnit::CountsPer360,
+            SensUnit::DegPerMm,
+        ];
+
+        for unit in &units {
+            let value = cm_per360_to_unit(unit, 30.0, 800, 1.02, 10);
+            assert!((unit_to_cm_per360(unit, value, 800, 1.02, 10) - 30.0).abs() < 1e-9);
+        }
+        assert!((cm_per360_to_unit(&SensUnit::InchesPer360, 25.4, 800, 1.0, 10) - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn pointer_speed_only_scales_games_without_raw_input() {
+        assert_eq!(pointer_speed_multiplier(10), 1.0);
+        assert_eq!(effective_yaw(0.022, true, 6), 0.022);
+        assert!(effective_yaw(0.022, false, 6) < 0.022);
+    }
+
+    #[test]
+    fn settable_sens_snaps_to_quarter_steps() {
+        let granularity = SensGranularity {
+            sens_step: Some(0.25),
+            ..SensGranularity::default()
+        };
+
+        assert_eq!(nearest_settable_sens(0.74, &granularity), 0.75);
+        assert_eq!(nearest_settable_sens(1.26, &granularity), 1.25);
+        assert_eq!(nearest_settable_sens(2.24, &granularity), 2.25);
+    }
+
+    #[test]
+    fn settable_sens_snaps_to_eighth_steps() {
+        let granularity = SensGranularity {
+            sens_step: Some(0.125),
+            ..SensGranularity::default()
+        };
+
+        assert_eq!(nearest_settable_sens(0.13, &granularity), 0.125);
+        assert_eq!(nearest_settable_sens(1.33, &granularity), 1.375);
+    }
+
+    #[test]
+    fn settable_sens_removes_float_noise() {
+        let granularity = SensGranularity {
+            sens_step: Some(0.1),
+            ..SensGranularity::default()
+        };
+
+        assert_eq!(nearest_settable_sens(0.31, &granularity), 0.3);
+    }
+
+    #[test]
+    fn settable_sens_is_clamped_to_the_range() {
+        let granularity = SensGranularity {
+            sens_min: Some(0.5),
+            sens_max: Some(10.0),
+            ..SensGranularity::default()
+        };
+
+        assert_eq!(nearest_settable_sens(0.1, &granularity), 0.5);
+        assert_eq!(nearest_settable_sens(12.0, &granularity), 10.0);
+    }
+
+    #[test]
+    fn convert_unit_rejects_zero_sensitivity() {
+        assert!(convert_unit(0.0, &game(0.022, None), 800, &game(0.07, None), 800, 1.0, 10).is_err());
+        assert!(convert_unit(0.0, &SensUnit::Cm360, 800, &game(0.022, None), 800, 1.0, 10).is_err());
+        assert!(convert_unit(f64::NAN, &SensUnit::Cm360, 800, &SensUnit::InchesPer360, 800, 1.0, 10).is_err());
+    }
+
+    #[test]
+    fn convert_unit_rejects_zero_dpi_and_yaw() {
+        assert!(convert_unit(1.0, &game(0.022, None), 0, &SensUnit::Cm360, 800, 1.0, 10).is_err());
+        assert!(convert_unit(1.0, &game(0.0, None), 800, &SensUnit::Cm360, 800, 1.0, 10).is_err());
+    }
+
+    #[test]
+    fn convert_unit_without_dpi_units_ignores_the_dpi() {
+        let conversion = convert_unit(30.48, &SensUnit::Cm360, 0, &SensUnit::InchesPer360, 0, 1.0, 10).unwrap();
+        assert!((conversion.sens - 12.0).abs() < 1e-9);
+
+        let conversion = convert_unit(36.0, &SensUnit::Cm360, 0, &SensUnit::DegPerMm, 0, 1.0, 10).unwrap();
+        assert!((conversion.sens - 1.0).abs() < 1e-9);
+        assert!(convert_unit(36.0, &SensUnit::Cm360, 0, &SensUnit::CountsPer360, 0, 1.0, 10).is_err());
+    }
+
+    #[test]
+    fn convert_unit_rejects_a_target_game_without_yaw() {
+        assert!(convert_unit(30.0, &SensUnit::Cm360, 0, &game(0.0, None), 800, 1.0, 10).is_err());
+        assert!(convert_unit(30.0, &SensUnit::Cm360, 0, &game(f64::NAN, None), 800, 1.0, 10).is_err());
+    }
+
+    #[test]
+    fn convert_unit_between_games_keeps_cm_per360() {
+        let conversion = convert_unit(1.0, &game(0.022, None), 800, &game(0.0066, None), 800, 1.0, 10).unwrap();
+
+        assert!((conversion.sens - 0.022 / 0.0066).abs() < 1e-9);
+        assert!(conversion.error_percent.abs() < 1e-9);
+    }
+
+    #[test]
+    fn suggest_dpi_ignores_invalid_targets() {
+        assert_eq!(suggest_dpi(0.0, 0.022, 1.0, &SensGranularity::default()), None);
+    }
+
+    #[test]
+    fn curve_yaw_interpolates_between_points() {
+        let curve = [(2.0, 0.03), (1.0, 0.02), (4.0, 0.05)];
+
+        assert!((curve_yaw(&curve, 1.5) - 0.025).abs() < 1e-12);
+        assert!((curve_yaw(&curve, 3.0) - 0.04).abs() < 1e-12);
+        assert_eq!(curve_yaw(&curve, 0.5), 0.02);
+        assert_eq!(curve_yaw(&curve, 10.0), 0.05);
+        assert_eq!(curve_yaw(&[], 1.0), 0.0);
+    }
+
+    #[test]
+    fn curve_yaw_survives_nan_points() {
+        let curve = [(1.0, 0.02), (f64::NAN, 0.5), (2.0, 0.03)];
+
+        assert!((curve_yaw(&curve, 1.5) - 0.025).abs() < 1e-12);
+    }
+
+    #[test]
+    fn fov_scales_at_16_9() {
+        // CS:GO's 90 at 4:3 stretched to 16:9 is 106.26 horizontal and 73.74 vertical
+        let horizontal = fov_to_horizontal(90.0, FovScale::Hor4ML3, 1920.0, 1080.0);
+        assert!((horizontal - 106.2602).abs() < 1e-4, "{}", horizontal);
+        assert!((horizontal_to_fov(horizontal, FovScale::Vertical, 1920.0, 1080.0) - 73.7398).abs() < 1e-4);
+
+        // Overwatch's 103 horizontal is 70.53 vertical
+        assert!((horizontal_to_fov(103.0, FovScale::Vertical, 1920.0, 1080.0) - 70.5328).abs() < 1e-4);
+        assert!((fov_to_horizontal(70.5328, FovScale::Vertical, 1920.0, 1080.0) - 103.0).abs() < 1e-3);
+        assert_eq!(fov_to_horizontal(103.0, FovScale::Horizontal, 1920.0, 1080.0), 103.0);
+    }
+
+    #[test]
+    fn fov_scales_are_the_same_at_4_3() {
+        let horizontal = fov_to_horizontal(90.0, FovScale::Hor4ML3, 1600.0, 1200.0);
+
+        assert!((horizontal - 90.0).abs() < 1e-9);
+        assert!((horizontal_to_fov(90.0, FovScale::Vertical, 1600.0, 1200.0) - 73.7398).abs() < 1e-4);
+    }
+
+    #[test]
+    fn angular_resolution_at_the_screen_center() {
+        // 90 degrees over 1920 pixels puts 960 pixels on tan(45) = 1
+        let resolution = calculate_angular_resolution(1.0, 0.022, 90.0, FovScale::Horizontal, 1920.0, 1080.0);
+
+        assert!((resolution.deg_per_count - 0.022).abs() < 1e-12);
+        assert!((resolution.horizontal_fov - 90.0).abs() < 1e-9);
+        assert!((resolution.pixels_per_degree - 960.0_f64.to_radians()).abs() < 1e-9);
+        assert!((resolution.pixels_per_count - 0.022 * 960.0_f64.to_radians()).abs() < 1e-9);
+        assert!((resolution.counts_per_pixel - 2.7129).abs() < 1e-4);
+        assert!(!resolution.skips_pixels);
+    }
+
+    #[test]
+    fn high_sens_skips_pixels() {
+        let resolution = calculate_angular_resolution(5.0, 0.022, 103.0, FovScale::Horizontal, 2560.0, 1440.0);
+
+        assert!(resolution.pixels_per_count > 1.0);
+        assert!(resolution.skips_pixels);
+        assert_eq!(
+            calculate_angular_resolution(0.0, 0.022, 103.0, FovScale::Horizontal, 2560.0, 1440.0).counts_per_pixel,
+            0.0
+        );
+    }
+}
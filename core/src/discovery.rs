@@ -0,0 +1,384 @@
+use crate::models::{GameYaw, InstalledGame};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Steam's KeyValues text format, as used by libraryfolders.vdf and the app manifests
+pub enum VdfValue {
+    Str(String),
+    Map(Vec<(String, VdfValue)>),
+}
+
+impl VdfValue {
+    pub fn get(&self, key: &str) -> Option<&VdfValue> {
+        match self {
+            VdfValue::Map(entries) => entries
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(key))
+                .map(|(_, v)| v),
+            VdfValue::Str(_) => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            VdfValue::Str(s) => Some(s),
+            VdfValue::Map(_) => None,
+        }
+    }
+}
+
+fn vdf_tokens(content: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                let mut token = String::new();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => {
+                            if let Some(escaped) = chars.next() {
+                                token.push(escaped);
+                            }
+                        }
+                        _ => token.push(c),
+                    }
+                }
+                tokens.push(token);
+            }
+            '{' | '}' => tokens.push(c.to_string()),
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    tokens
+}
+
+fn vdf_map(tokens: &[String], pos: &mut usize) -> Vec<(String, VdfValue)> {
+    let mut entries = Vec::new();
+
+    while *pos < tokens.len() && tokens[*pos] != "}" {
+        let key = tokens[*pos].clone();
+        *pos += 1;
+        if *pos >= tokens.len() {
+            break;
+        }
+
+        if tokens[*pos] == "{" {
+            *pos += 1;
+            let map = vdf_map(tokens, pos);
+            *pos += 1; // closing brace
+            entries.push((key, VdfValue::Map(map)));
+        } else {
+            entries.push((key, VdfValue::Str(tokens[*pos].clone())));
+            *pos += 1;
+        }
+    }
+
+    entries
+}
+
+pub fn parse_vdf(content: &str) -> VdfValue {
+    let tokens = vdf_tokens(content);
+    VdfValue::Map(vdf_map(&tokens, &mut 0))
+}
+
+// Where Steam is usually installed, the ones that exist are scanned
+pub fn default_steam_roots(home: Option<PathBuf>) -> Vec<PathBuf> {
+    let mut roots = vec![
+        PathBuf::from("C:\\Program Files (x86)\\Steam"),
+        PathBuf::from("C:\\Program Files\\Steam"),
+    ];
+
+    if let Some(home) = home {
+        roots.push(home.join(".steam").join("steam"));
+        roots.push(home.join(".local").join("share").join("Steam"));
+        // Flatpak install
+        roots.push(home.join(".var/app/com.valvesoftware.Steam/.local/share/Steam"));
+        roots.push(home.join("Library/Application Support/Steam"));
+    }
+
+    roots.into_iter().filter(|root| root.is_dir()).collect()
+}
+
+// Library paths in a parsed libraryfolders.vdf. Newer files have a block with a path per library,
+// older ones the path under a numbered key next to settings like TimeNextStatsReport.
+pub fn library_paths(vdf: &VdfValue) -> Vec<PathBuf> {
+    let Some(VdfValue::Map(entries)) = vdf.get("libraryfolders") else {
+        return Vec::new();
+    };
+
+    entries
+        .iter()
+        .filter_map(|(key, entry)| match entry {
+            VdfValue::Map(_) => entry.get("path").and_then(VdfValue::as_str),
+            VdfValue::Str(path) if key.parse::<u32>().is_ok() => Some(path.as_str()),
+            VdfValue::Str(_) => None,
+        })
+        .map(PathBuf::from)
+        .collect()
+}
+
+// Library folders listed in libraryfolders.vdf, the Steam root itself is always one
+pub fn steam_libraries(steam_root: &Path) -> Vec<PathBuf> {
+    let mut libraries = vec![steam_root.to_path_buf()];
+
+    let path = steam_root.join("steamapps").join("libraryfolders.vdf");
+    if let Ok(content) = fs::read_to_string(path) {
+        libraries.extend(library_paths(&parse_vdf(&content)));
+    }
+
+    // ~/.steam/steam is often a link to ~/.local/share/Steam, so work with the resolved paths
+    let mut resolved: Vec<PathBuf> = Vec::new();
+    for library in libraries.iter().filter_map(|library| fs::canonicalize(library).ok()) {
+        if library.is_dir() && !resolved.contains(&library) {
+            resolved.push(library);
+        }
+    }
+    resolved
+}
+
+pub fn steam_library_games(library: &Path) -> Vec<InstalledGame> {
+    let steamapps = library.join("steamapps");
+    let entries = match fs::read_dir(&steamapps) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut games = Vec::new();
+    for entry in entries.flatten() {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if !file_name.starts_with("appmanifest_") || !file_name.ends_with(".acf") {
+            continue;
+        }
+
+        let content = match fs::read_to_string(entry.path()) {
+            Ok(content) => content,
+            Err(_) => continue,
+        };
+        let vdf = parse_vdf(&content);
+        let app = match vdf.get("AppState") {
+            Some(app) => app,
+            None => continue,
+        };
+
+        let field = |key: &str| app.get(key).and_then(VdfValue::as_str).map(str::to_string);
+        if let (Some(name), Some(install_dir)) = (field("name"), field("installdir")) {
+            games.push(InstalledGame {
+                name,
+                app_id: field("appid").and_then(|id| id.parse().ok()),
+                install_dir: steamapps.join("common").join(install_dir).to_string_lossy().to_string(),
+                library: library.to_string_lossy().to_string(),
+                matched: None,
+            });
+        }
+    }
+
+    games.sort_by(|a, b| a.name.cmp(&b.name));
+    games
+}
+
+// Every folder inside a user-defined folder counts as a game named like the folder
+pub fn folder_games(folder: &Path) -> Vec<InstalledGame> {
+    let entries = match fs::read_dir(folder) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut games: Vec<InstalledGame> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| InstalledGame {
+            name: entry.file_name().to_string_lossy().to_string(),
+            app_id: None,
+            install_dir: entry.path().to_string_lossy().to_string(),
+            library: folder.to_string_lossy().to_string(),
+            matched: None,
+        })
+        .collect();
+
+    games.sort_by(|a, b| a.name.cmp(&b.name));
+    games
+}
+
+// Lowercase letters and digits only, so "Counter-Strike 2" matches "counter strike 2"
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+// Matches by Steam app ID first and falls back to the name
+pub fn match_games(installed: &mut [InstalledGame], games: &[GameYaw]) {
+    for game in installed.iter_mut() {
+        let by_id = game.app_id.and_then(|id| games.iter().find(|g| g.steam_app_id == Some(id)));
+        let by_name = || {
+            let name = normalize_name(&game.name);
+            games.iter().find(|g| normalize_name(&g.name) == name)
+        };
+        game.matched = by_id.or_else(by_name).map(|g| g.name.clone());
+    }
+}
+
+pub fn discover_games(steam_roots: &[PathBuf], folders: &[PathBuf], games: &[GameYaw]) -> Vec<InstalledGame> {
+    let mut installed = Vec::new();
+    let mut libraries: Vec<PathBuf> = Vec::new();
+
+    for root in steam_roots {
+        for library in steam_libraries(root) {
+            if !libraries.contains(&library) {
+                installed.extend(steam_library_games(&library));
+                libraries.push(library);
+            }
+        }
+    }
+    for folder in folders {
+        installed.extend(folder_games(folder));
+    }
+
+    match_games(&mut installed, games);
+    installed
+}
+
+// Config paths in Games.json can start with {install_dir} to be relative to where the game was found
+pub fn resolve_config_path(path: &str, install_dir: Option<&str>) -> Option<PathBuf> {
+    match path.strip_prefix("{install_dir}") {
+        Some(rest) => install_dir.map(|dir| Path::new(dir).join(rest.trim_start_matches(['/', '\\']))),
+        None => Some(PathBuf::from(path)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ScopeConvention, SensGranularity};
+
+    const OLD_LIBRARY_FOLDERS: &str = r#""LibraryFolders"
+{
+	"TimeNextStatsReport"		"1561832478"
+	"ContentStatsID"		"-158337411"
+	"1"		"{library}"
+}
+"#;
+
+    const NEW_LIBRARY_FOLDERS: &str = r#""libraryfolders"
+{
+	"0"
+	{
+		"path"		"{root}"
+		"label"		""
+		"contentid"		"-158337411"
+		"apps"
+		{
+			"730"		"35678423442"
+		}
+	}
+	"1"
+	{
+		"path"		"{library}"
+		"label"		"Games"
+	}
+}
+"#;
+
+    fn manifest(app_id: u32, name: &str, install_dir: &str) -> String {
+        format!(
+            "\"AppState\"\n{{\n\t\"appid\"\t\t\"{}\"\n\t\"name\"\t\t\"{}\"\n\t\"installdir\"\t\t\"{}\"\n}}\n",
+            app_id, name, install_dir
+        )
+    }
+
+    fn game(name: &str, steam_app_id: Option<u32>) -> GameYaw {
+        GameYaw {
+            name: name.to_string(),
+            yaw: 0.022,
+            yaw_curve: None,
+            raw_input: true,
+            granularity: SensGranularity::default(),
+            scopes: Vec::new(),
+            scope_convention: ScopeConvention::default(),
+            gamepad: None,
+            config: None,
+            steam_app_id,
+        }
+    }
+
+    // A Steam root with libraryfolders.vdf and a second library next to it
+    fn fake_steam(vdf: &str) -> (tempfile::TempDir, PathBuf, PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("Steam");
+        let library = dir.path().join("SteamLibrary");
+        fs::create_dir_all(root.join("steamapps")).unwrap();
+        fs::create_dir_all(library.join("steamapps")).unwrap();
+
+        let vdf = vdf
+            .replace("{root}", &root.to_string_lossy())
+            .replace("{library}", &library.to_string_lossy());
+        fs::write(root.join("steamapps").join("libraryfolders.vdf"), vdf).unwrap();
+
+        let root = fs::canonicalize(root).unwrap();
+        let library = fs::canonicalize(library).unwrap();
+        (dir, root, library)
+    }
+
+    #[test]
+    fn old_format_only_takes_numbered_libraries() {
+        let vdf = parse_vdf(&OLD_LIBRARY_FOLDERS.replace("{library}", "D:\\\\SteamLibrary"));
+
+        assert_eq!(library_paths(&vdf), vec![PathBuf::from("D:\\SteamLibrary")]);
+    }
+
+    #[test]
+    fn old_format_libraries_are_found() {
+        let (_dir, root, library) = fake_steam(OLD_LIBRARY_FOLDERS);
+
+        assert_eq!(steam_libraries(&root), vec![root, library]);
+    }
+
+    #[test]
+    fn new_format_libraries_are_found_once() {
+        let (_dir, root, library) = fake_steam(NEW_LIBRARY_FOLDERS);
+
+        assert_eq!(steam_libraries(&root), vec![root, library]);
+    }
+
+    #[test]
+    fn app_manifests_are_matched_to_games() {
+        let (_dir, root, library) = fake_steam(NEW_LIBRARY_FOLDERS);
+        fs::write(root.join("steamapps/appmanifest_730.acf"), manifest(730, "Counter-Strike 2", "Counter-Strike Global Offensive")).unwrap();
+        fs::write(library.join("steamapps/appmanifest_1172470.acf"), manifest(1172470, "Apex Legends", "Apex Legends")).unwrap();
+        fs::write(library.join("steamapps/appmanifest_570.acf"), manifest(570, "Dota 2", "dota 2 beta")).unwrap();
+        fs::write(library.join("steamapps/libraryfolders.vdf"), "not a manifest").unwrap();
+
+        // CS2 is matched by its app ID even though the name differs, Apex by name
+        let games = [game("CS2", Some(730)), game("apex legends", None)];
+        let installed = discover_games(std::slice::from_ref(&root), &[], &games);
+
+        let summary: Vec<(String, Option<u32>, Option<String>)> = installed
+            .iter()
+            .map(|game| (game.name.clone(), game.app_id, game.matched.clone()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Counter-Strike 2".to_string(), Some(730), Some("CS2".to_string())),
+                ("Apex Legends".to_string(), Some(1172470), Some("apex legends".to_string())),
+                ("Dota 2".to_string(), Some(570), None),
+            ]
+        );
+        assert_eq!(
+            installed[0].install_dir,
+            root.join("steamapps/common/Counter-Strike Global Offensive").to_string_lossy()
+        );
+    }
+}
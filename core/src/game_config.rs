@@ -0,0 +1,499 @@
+use crate::models::{ConfigFormat, GameConfig, GameConfigValues};
+use serde_json::Value;
+
+// A config file format the sensitivity can be read from and written to. Adapters work on the
+// file content, so reading and writing the file itself stays in one place.
+pub trait ConfigAdapter {
+    // Setting name used when the game entry doesn't name one
+    fn default_key(&self) -> &'static str;
+
+    // Setting that holds the yaw, for formats that have one
+    fn yaw_key(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn read_value(&self, content: &str, key: &str) -> Option<f64>;
+
+    // Returns the new content with the value replaced, everything else stays as it was
+    fn write_value(&self, content: &str, key: &str, value: f64) -> Result<String, String>;
+}
+
+// Console variable files, one `[command] name "value"` per line
+pub struct CfgAdapter {
+    pub default_key: &'static str,
+    // Command written in front of new lines, like seta in Quake 3
+    pub set_command: Option<&'static str>,
+}
+
+const CFG_SET_COMMANDS: [&str; 4] = ["seta", "set", "sets", "setu"];
+
+impl CfgAdapter {
+    // Value of the line if it sets the key
+    fn line_value<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+        let line = line.split("//").next().unwrap_or("");
+        let mut tokens = line.split_whitespace();
+        let mut name = tokens.next()?;
+        if CFG_SET_COMMANDS.contains(&name.to_lowercase().as_str()) {
+            name = tokens.next()?;
+        }
+
+        if name.trim_matches('"').eq_ignore_ascii_case(key) {
+            tokens.next().map(|value| value.trim_matches('"'))
+        } else {
+            None
+        }
+    }
+}
+
+impl ConfigAdapter for CfgAdapter {
+    fn default_key(&self) -> &'static str {
+        self.default_key
+    }
+
+    fn yaw_key(&self) -> Option<&'static str> {
+        Some("m_yaw")
+    }
+
+    fn read_value(&self, content: &str, key: &str) -> Option<f64> {
+        // Later lines win when the game runs the file
+        content
+            .lines()
+            .rev()
+            .find_map(|line| CfgAdapter::line_value(line, key))
+            .and_then(|value| value.parse().ok())
+    }
+
+    fn write_value(&self, content: &str, key: &str, value: f64) -> Result<String, String> {
+        let mut found = false;
+        let mut lines: Vec<String> = content
+            .lines()
+            .map(|line| match CfgAdapter::line_value(line, key) {
+                Some(old) => {
+                    found = true;
+                    // old points into line, replace it by position so a key containing the value stays intact
+                    let start = old.as_ptr() as usize - line.as_ptr() as usize;
+                    format!("{}{}{}", &line[..start], format_value(value), &line[start + old.len()..])
+                }
+                None => line.to_string(),
+            })
+            .collect();
+
+        if !found {
+            lines.push(match self.set_command {
+                Some(command) => format!("{} {} \"{}\"", command, key, format_value(value)),
+                None => format!("{} \"{}\"", key, format_value(value)),
+            });
+        }
+
+        Ok(join_lines(content, lines))
+    }
+}
+
+// Ini files with `Key=Value` lines
+pub struct IniAdapter;
+
+impl IniAdapter {
+    fn line_value<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with(';') || trimmed.starts_with('#') {
+            return None;
+        }
+
+        let (name, value) = trimmed.split_once('=')?;
+        if name.trim().eq_ignore_ascii_case(key) {
+            Some(value.trim())
+        } else {
+            None
+        }
+    }
+}
+
+impl ConfigAdapter for IniAdapter {
+    fn default_key(&self) -> &'static str {
+        "MouseSensitivity"
+    }
+
+    fn read_value(&self, content: &str, key: &str) -> Option<f64> {
+        content
+            .lines()
+            .find_map(|line| IniAdapter::line_value(line, key))
+            .and_then(|value| value.parse().ok())
+    }
+
+    // New keys aren't added, the section they belong in differs between games
+    fn write_value(&self, content: &str, key: &str, value: f64) -> Result<String, String> {
+        let mut found = false;
+        let lines: Vec<String> = content
+            .lines()
+            .map(|line| match IniAdapter::line_value(line, key) {
+                Some(_) if !found => {
+                    found = true;
+                    let (name, old) = line.split_once('=').unwrap();
+                    let padding = &old[..old.len() - old.trim_start().len()];
+                    format!("{}={}{}", name, padding, format_value(value))
+                }
+                _ => line.to_string(),
+            })
+            .collect();
+
+        if !found {
+            return Err(format!("{} not found in config", key));
+        }
+        Ok(join_lines(content, lines))
+    }
+}
+
+// JSON settings files, the key is a dotted path like "input.mouse.sensitivity"
+pub struct JsonAdapter;
+
+impl JsonAdapter {
+    // Byte range of the value at the path. The text is scanned instead of parsed into a tree, so
+    // a write can replace just that value and leave the formatting of the file alone.
+    fn value_range(content: &str, key: &str) -> Option<(usize, usize)> {
+        let bytes = content.as_bytes();
+        let mut start = skip_json_whitespace(bytes, 0);
+
+        for part in key.split('.') {
+            if bytes.get(start) != Some(&b'{') {
+                return None;
+            }
+            let mut position = skip_json_whitespace(bytes, start + 1);
+            let mut found = None;
+
+            while bytes.get(position) == Some(&b'"') {
+                let name_end = json_value_end(bytes, position)?;
+                let name: String = serde_json::from_str(&content[position..name_end]).ok()?;
+                let colon = skip_json_whitespace(bytes, name_end);
+                let value_start = skip_json_whitespace(bytes, colon + 1);
+                let value_end = json_value_end(bytes, value_start)?;
+
+                if name == part {
+                    found = Some(value_start);
+                    break;
+                }
+                position = skip_json_whitespace(bytes, value_end);
+                if bytes.get(position) == Some(&b',') {
+                    position = skip_json_whitespace(bytes, position + 1);
+                }
+            }
+            start = found?;
+        }
+
+        Some((start, json_value_end(bytes, start)?))
+    }
+}
+
+fn skip_json_whitespace(bytes: &[u8], mut position: usize) -> usize {
+    while bytes.get(position).is_some_and(|b| b.is_ascii_whitespace()) {
+        position += 1;
+    }
+    position
+}
+
+// End of the value starting at the position, for content that is known to be valid JSON
+fn json_value_end(bytes: &[u8], start: usize) -> Option<usize> {
+    match bytes.get(start)? {
+        b'"' => {
+            let mut position = start + 1;
+            loop {
+                match bytes.get(position)? {
+                    b'\\' => position += 2,
+                    b'"' => return Some(position + 1),
+                    _ => position += 1,
+                }
+            }
+        }
+        b'{' | b'[' => {
+            let mut depth = 0;
+            let mut position = start;
+            loop {
+                match bytes.get(position)? {
+                    b'"' => {
+                        position = json_value_end(bytes, position)?;
+                        continue;
+                    }
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Some(position + 1);
+                        }
+                    }
+                    _ => {}
+                }
+                position += 1;
+            }
+        }
+        _ => {
+            let length = bytes[start..]
+                .iter()
+                .position(|b| b.is_ascii_whitespace() || matches!(b, b',' | b'}' | b']'))
+                .unwrap_or(bytes.len() - start);
+            Some(start + length)
+        }
+    }
+}
+
+impl ConfigAdapter for JsonAdapter {
+    fn default_key(&self) -> &'static str {
+        "sensitivity"
+    }
+
+    fn read_value(&self, content: &str, key: &str) -> Option<f64> {
+        let root: Value = serde_json::from_str(content).ok()?;
+        let value = key.split('.').try_fold(&root, |value, part| value.get(part))?;
+
+        match value {
+            Value::Number(number) => number.as_f64(),
+            Value::String(string) => string.parse().ok(),
+            _ => None,
+        }
+    }
+
+    fn write_value(&self, content: &str, key: &str, value: f64) -> Result<String, String> {
+        // Only valid files are patched, the scanner relies on it
+        serde_json::from_str::<Value>(content).map_err(|e| e.to_string())?;
+
+        let (start, end) = JsonAdapter::value_range(content, key)
+            .ok_or_else(|| format!("{} not found in config", key))?;
+        let old = &content[start..end];
+
+        // Keep the type the game wrote, some store numbers as strings and some only take integers
+        let new = if old.starts_with('"') {
+            format!("\"{}\"", format_value(value))
+        } else if old.starts_with(|c: char| c == '-' || c.is_ascii_digit()) {
+            let integer = !old.contains(['.', 'e', 'E']);
+            if integer && value.fract() == 0.0 {
+                format!("{}", value as i64)
+            } else {
+                let formatted = format_value(value);
+                if formatted.contains('.') {
+                    formatted
+                } else {
+                    format!("{}.0", formatted)
+                }
+            }
+        } else {
+            return Err(format!("{} is not a number", key));
+        };
+
+        Ok(format!("{}{}{}", &content[..start], new, &content[end..]))
+    }
+}
+
+// XML settings files, as an element `<key>2.5</key>` or an attribute `key="2.5"`
+pub struct XmlAdapter;
+
+impl XmlAdapter {
+    // Byte range of the value in the content
+    fn value_range(content: &str, key: &str) -> Option<(usize, usize)> {
+        let open = format!("<{}>", key);
+        if let Some(start) = content.find(&open) {
+            let start = start + open.len();
+            let end = start + content[start..].find(&format!("</{}>", key))?;
+            return Some((start, end));
+        }
+
+        let attribute = format!(" {}=\"", key);
+        let start = content.find(&attribute)? + attribute.len();
+        let end = start + content[start..].find('"')?;
+        Some((start, end))
+    }
+}
+
+impl ConfigAdapter for XmlAdapter {
+    fn default_key(&self) -> &'static str {
+        "MouseSensitivity"
+    }
+
+    fn read_value(&self, content: &str, key: &str) -> Option<f64> {
+        let (start, end) = XmlAdapter::value_range(content, key)?;
+        content[start..end].trim().parse().ok()
+    }
+
+    fn write_value(&self, content: &str, key: &str, value: f64) -> Result<String, String> {
+        let (start, end) = XmlAdapter::value_range(content, key)
+            .ok_or_else(|| format!("{} not found in config", key))?;
+        Ok(format!("{}{}{}", &content[..start], format_value(value), &content[end..]))
+    }
+}
+
+pub fn config_adapter(format: ConfigFormat) -> Box<dyn ConfigAdapter> {
+    match format {
+        ConfigFormat::Source => Box::new(CfgAdapter {
+            default_key: "sensitivity",
+            set_command: None,
+        }),
+        ConfigFormat::Quake => Box::new(CfgAdapter {
+            default_key: "sensitivity",
+            set_command: Some("seta"),
+        }),
+        ConfigFormat::Unreal => Box::new(IniAdapter),
+        ConfigFormat::Json => Box::new(JsonAdapter),
+        ConfigFormat::Xml => Box::new(XmlAdapter),
+    }
+}
+
+// Enough digits for any sensitivity slider without float noise like 2.5000000001
+fn format_value(value: f64) -> String {
+    let formatted = format!("{:.6}", value);
+    formatted.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+fn join_lines(content: &str, lines: Vec<String>) -> String {
+    let newline = if content.contains("\r\n") { "\r\n" } else { "\n" };
+    let mut joined = lines.join(newline);
+    if content.is_empty() || content.ends_with('\n') {
+        joined.push_str(newline);
+    }
+    joined
+}
+
+pub fn read_config_values(config: &GameConfig, content: &str) -> GameConfigValues {
+    let adapter = config_adapter(config.format);
+    let key = config.key.as_deref().unwrap_or(adapter.default_key());
+
+    GameConfigValues {
+        path: config.path.clone(),
+        sens: adapter.read_value(content, key),
+        yaw: adapter.yaw_key().and_then(|yaw_key| adapter.read_value(content, yaw_key)),
+    }
+}
+
+// Checked here for every format, so no adapter ever writes a value the game can't read
+pub fn write_config_sens(config: &GameConfig, content: &str, sens: f64) -> Result<String, String> {
+    if !sens.is_finite() || sens <= 0.0 {
+        return Err("Sensitivity must be a number greater than zero".to_string());
+    }
+
+    let adapter = config_adapter(config.format);
+    let key = config.key.as_deref().unwrap_or(adapter.default_key());
+    adapter.write_value(content, key, sens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE_CFG: &str = include_str!("../tests/fixtures/game_config/config.cfg");
+    const QUAKE_CFG: &str = include_str!("../tests/fixtures/game_config/q3config.cfg");
+    const UNREAL_INI: &str = include_str!("../tests/fixtures/game_config/GameUserSettings.ini");
+    const SETTINGS_JSON: &str = include_str!("../tests/fixtures/game_config/settings.json");
+
+    fn config(format: ConfigFormat, key: Option<&str>) -> GameConfig {
+        GameConfig {
+            format,
+            path: String::new(),
+            key: key.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn cfg_reads_sens_and_yaw() {
+        let values = read_config_values(&config(ConfigFormat::Source, None), SOURCE_CFG);
+
+        assert_eq!(values.sens, Some(2.5));
+        assert_eq!(values.yaw, Some(0.022));
+    }
+
+    #[test]
+    fn cfg_write_only_changes_the_value() {
+        let written = write_config_sens(&config(ConfigFormat::Source, None), SOURCE_CFG, 1.25).unwrap();
+
+        assert_eq!(written, SOURCE_CFG.replace("sensitivity \"2.5\"", "sensitivity \"1.25\""));
+    }
+
+    #[test]
+    fn cfg_adds_a_missing_key() {
+        let config = config(ConfigFormat::Quake, Some("cl_mouseAccel"));
+        assert_eq!(read_config_values(&config, QUAKE_CFG).sens, None);
+
+        let written = write_config_sens(&config, QUAKE_CFG, 0.5).unwrap();
+        assert!(written.starts_with(QUAKE_CFG));
+        assert!(written.ends_with("seta cl_mouseAccel \"0.5\"\n"));
+        assert_eq!(read_config_values(&config, &written).sens, Some(0.5));
+    }
+
+    #[test]
+    fn quake_cfg_reads_and_writes_seta_lines() {
+        let config = config(ConfigFormat::Quake, None);
+        assert_eq!(read_config_values(&config, QUAKE_CFG).sens, Some(3.2));
+
+        let written = write_config_sens(&config, QUAKE_CFG, 2.0).unwrap();
+        assert_eq!(written, QUAKE_CFG.replace("seta sensitivity \"3.2\"", "seta sensitivity \"2\""));
+    }
+
+    #[test]
+    fn ini_reads_sens() {
+        let values = read_config_values(&config(ConfigFormat::Unreal, None), UNREAL_INI);
+
+        assert_eq!(values.sens, Some(0.35));
+        assert_eq!(values.yaw, None);
+    }
+
+    #[test]
+    fn ini_write_keeps_spacing_and_other_lines() {
+        let written = write_config_sens(&config(ConfigFormat::Unreal, None), UNREAL_INI, 0.4).unwrap();
+
+        assert_eq!(written, UNREAL_INI.replace("MouseSensitivity = 0.350000", "MouseSensitivity = 0.4"));
+    }
+
+    #[test]
+    fn ini_refuses_a_missing_key() {
+        let config = config(ConfigFormat::Unreal, Some("MouseSensitivityADS"));
+
+        assert_eq!(read_config_values(&config, UNREAL_INI).sens, None);
+        assert!(write_config_sens(&config, UNREAL_INI, 0.4).is_err());
+    }
+
+    #[test]
+    fn json_reads_a_dotted_path() {
+        let values = read_config_values(&config(ConfigFormat::Json, Some("input.mouse.sensitivity")), SETTINGS_JSON);
+
+        assert_eq!(values.sens, Some(1.75));
+    }
+
+    #[test]
+    fn json_write_keeps_the_formatting() {
+        let config = config(ConfigFormat::Json, Some("input.mouse.sensitivity"));
+        let written = write_config_sens(&config, SETTINGS_JSON, 2.0).unwrap();
+
+        assert_eq!(written, SETTINGS_JSON.replace("\"sensitivity\": 1.75", "\"sensitivity\": 2.0"));
+    }
+
+    #[test]
+    fn json_write_keeps_integers() {
+        let config = config(ConfigFormat::Json, Some("video.fov"));
+
+        let written = write_config_sens(&config, SETTINGS_JSON, 90.0).unwrap();
+        assert_eq!(written, SETTINGS_JSON.replace("\"fov\": 103", "\"fov\": 90"));
+
+        let written = write_config_sens(&config, SETTINGS_JSON, 90.5).unwrap();
+        assert_eq!(written, SETTINGS_JSON.replace("\"fov\": 103", "\"fov\": 90.5"));
+    }
+
+    #[test]
+    fn json_refuses_a_missing_key() {
+        let config = config(ConfigFormat::Json, Some("input.mouse.acceleration"));
+
+        assert_eq!(read_config_values(&config, SETTINGS_JSON).sens, None);
+        assert!(write_config_sens(&config, SETTINGS_JSON, 1.0).is_err());
+    }
+
+    #[test]
+    fn invalid_sens_is_never_written() {
+        let formats = [
+            ConfigFormat::Source,
+            ConfigFormat::Quake,
+            ConfigFormat::Unreal,
+            ConfigFormat::Json,
+            ConfigFormat::Xml,
+        ];
+        for format in formats {
+            let config = config(format, None);
+            for sens in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+                assert!(write_config_sens(&config, SOURCE_CFG, sens).is_err());
+            }
+        }
+    }
+}
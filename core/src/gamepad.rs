@@ -0,0 +1,113 @@
+use crate::models::{GamepadConversion, GamepadProfile};
+
+// Number of deflection points sampled when comparing two response curves
+const CURVE_SAMPLES: usize = 10;
+
+// Turn rate in deg/s at a stick deflection between 0 and 1, after holding it for the given time
+pub fn turn_rate(profile: &GamepadProfile, sens: f64, deflection: f64, held: f64) -> f64 {
+    let deflection = deflection.abs().min(1.0);
+    if deflection <= profile.deadzone {
+        return 0.0;
+    }
+
+    let normalized = (deflection - profile.deadzone) / (1.0 - profile.deadzone);
+    let mut rate = profile.rate_per_sens * sens * normalized.powf(profile.exponent);
+
+    // Acceleration only kicks in when the stick is pushed all the way
+    if deflection >= 1.0 && profile.accel_boost > 0.0 {
+        let ramp = if profile.accel_time > 0.0 { (held / profile.accel_time).min(1.0) } else { 1.0 };
+        rate *= 1.0 + profile.accel_boost * ramp;
+    }
+
+    rate
+}
+
+// Mouse cm/360 that turns as fast as the stick when moving the mouse at the given speed in cm/s
+pub fn equivalent_cm_per360(rate: f64, mouse_speed: f64) -> f64 {
+    if rate <= 0.0 {
+        return 0.0;
+    }
+    360.0 / rate * mouse_speed
+}
+
+// Matches the turn rate at the given deflection after holding the stick for `held` seconds, the rest
+// of the curve follows the destination game
+pub fn convert_gamepad_sensitivity(
+    from: &GamepadProfile,
+    sens: f64,
+    to: &GamepadProfile,
+    deflection: f64,
+    held: f64,
+    mouse_speed: f64,
+) -> GamepadConversion {
+    let target_rate = turn_rate(from, sens, deflection, held);
+    let unit_rate = turn_rate(to, 1.0, deflection, held);
+    let new_sens = if unit_rate > 0.0 { target_rate / unit_rate } else { 0.0 };
+
+    let samples = (1..=CURVE_SAMPLES)
+        .map(|i| {
+            let d = i as f64 / CURVE_SAMPLES as f64;
+            (d, turn_rate(from, sens, d, held), turn_rate(to, new_sens, d, held))
+        })
+        .collect();
+
+    GamepadConversion {
+        sens: new_sens,
+        turn_rate: target_rate,
+        cm_per360: equivalent_cm_per360(target_rate, mouse_speed),
+        samples,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(accel_boost: f64, accel_time: f64) -> GamepadProfile {
+        GamepadProfile {
+            rate_per_sens: 100.0,
+            exponent: 2.0,
+            deadzone: 0.1,
+            accel_boost,
+            accel_time,
+        }
+    }
+
+    #[test]
+    fn acceleration_ramps_up_at_full_deflection() {
+        let accel = profile(0.5, 2.0);
+
+        assert_eq!(turn_rate(&accel, 1.0, 1.0, 0.0), 100.0);
+        assert_eq!(turn_rate(&accel, 1.0, 1.0, 1.0), 125.0);
+        assert_eq!(turn_rate(&accel, 1.0, 1.0, 5.0), 150.0);
+        // Below full deflection the boost doesn't apply
+        assert_eq!(turn_rate(&accel, 1.0, 0.55, 5.0), turn_rate(&profile(0.0, 0.0), 1.0, 0.55, 5.0));
+    }
+
+    #[test]
+    fn profiles_differing_only_in_acceleration() {
+        let plain = profile(0.0, 0.0);
+        let accel = profile(0.5, 2.0);
+
+        // Right as the stick is pushed both turn at the same rate
+        let start = convert_gamepad_sensitivity(&accel, 2.0, &plain, 1.0, 0.0, 10.0);
+        assert!((start.sens - 2.0).abs() < 1e-9);
+
+        // Once the ramp is done the game without acceleration needs 1.5x the sensitivity
+        let ramped = convert_gamepad_sensitivity(&accel, 2.0, &plain, 1.0, 2.0, 10.0);
+        assert!((ramped.sens - 3.0).abs() < 1e-9);
+        assert!((ramped.turn_rate - 300.0).abs() < 1e-9);
+        assert!((ramped.cm_per360 - 12.0).abs() < 1e-9);
+
+        let back = convert_gamepad_sensitivity(&plain, 3.0, &accel, 1.0, 2.0, 10.0);
+        assert!((back.sens - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn deflection_inside_the_deadzone_gives_no_sensitivity() {
+        let conversion = convert_gamepad_sensitivity(&profile(0.0, 0.0), 1.0, &profile(0.0, 0.0), 0.05, 0.0, 10.0);
+
+        assert_eq!(conversion.sens, 0.0);
+        assert_eq!(conversion.cm_per360, 0.0);
+    }
+}
@@ -0,0 +1,154 @@
+use crate::batch::csv_field;
+use crate::models::{HistoryFilter, MeasurementKind, MeasurementRecord, TableFormat, UserSettings};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// The record is for the game set in the profile
+pub fn new_record(
+    kind: MeasurementKind,
+    method: &str,
+    counts: i32,
+    value: f64,
+    device: Option<String>,
+    profile: &UserSettings,
+    dpi_correction: f64,
+) -> MeasurementRecord {
+    MeasurementRecord {
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.as_secs())
+            .unwrap_or_default(),
+        kind,
+        method: method.to_string(),
+        game: profile.game.clone(),
+        device,
+        counts,
+        value,
+        profile: profile.clone(),
+        dpi_correction,
+    }
+}
+
+// Games and devices match without regard to case, so a filter typed by hand still finds them
+pub fn filter_history(records: &[MeasurementRecord], filter: &HistoryFilter) -> Vec<MeasurementRecord> {
+    let matches = |value: &Option<String>, wanted: &Option<String>| match wanted {
+        Some(wanted) => value.as_ref().is_some_and(|value| value.eq_ignore_ascii_case(wanted)),
+        None => true,
+    };
+
+    let mut filtered: Vec<MeasurementRecord> = records
+        .iter()
+        .filter(|record| filter.kind.is_none_or(|kind| record.kind == kind))
+        .filter(|record| matches(&record.game, &filter.game))
+        .filter(|record| matches(&record.device, &filter.device))
+        .filter(|record| filter.since.is_none_or(|since| record.timestamp >= since))
+        .filter(|record| filter.until.is_none_or(|until| record.timestamp <= until))
+        .cloned()
+        .collect();
+
+    if let Some(limit) = filter.limit {
+        let skip = filtered.len().saturating_sub(limit);
+        filtered.drain(..skip);
+    }
+
+    filtered
+}
+
+pub fn kind_name(kind: MeasurementKind) -> &'static str {
+    match kind {
+        MeasurementKind::Cm360 => "cm360",
+        MeasurementKind::Fov => "fov",
+        MeasurementKind::Yaw => "yaw",
+    }
+}
+
+pub fn format_history(records: &[MeasurementRecord], format: TableFormat) -> String {
+    let mut table = String::new();
+
+    match format {
+        TableFormat::Csv => {
+            table.push_str("timestamp,kind,method,game,device,counts,value,cm360,dpi,dpi_correction\n");
+            for record in records {
+                table.push_str(&format!(
+                    "{},{},{},{},{},{},{:.6},{:.3},{},{:.4}\n",
+                    record.timestamp,
+                    kind_name(record.kind),
+                    csv_field(&record.method),
+                    csv_field(record.game.as_deref().unwrap_or("")),
+                    csv_field(record.device.as_deref().unwrap_or("")),
+                    record.counts,
+                    record.value,
+                    record.profile.cm360,
+                    record.profile.dpi,
+                    record.dpi_correction
+                ));
+            }
+        }
+        TableFormat::Markdown => {
+            table.push_str("| Time | Kind | Method | Game | Device | Counts | Value | cm/360 | DPI |\n");
+            table.push_str("|---:|---|---|---|---|---:|---:|---:|---:|\n");
+            for record in records {
+                table.push_str(&format!(
+                    "| {} | {} | {} | {} | {} | {} | {:.6} | {:.3} | {} |\n",
+                    record.timestamp,
+                    kind_name(record.kind),
+                    record.method,
+                    record.game.as_deref().unwrap_or("").replace('|', "\\|"),
+                    record.device.as_deref().unwrap_or("").replace('|', "\\|"),
+                    record.counts,
+                    record.value,
+                    record.profile.cm360,
+                    record.profile.dpi
+                ));
+            }
+        }
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(game: Option<&str>) -> UserSettings {
+        UserSettings {
+            cm360: 30.0,
+            dpi: 800,
+            normal_fov: 103.0,
+            scoped_fov: 40.0,
+            game_sens: 1.0,
+            game_fov: 103.0,
+            game: game.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn records_carry_the_profile_game() {
+        let record = new_record(MeasurementKind::Fov, "tracked_turn", 2000, 103.0, None, &profile(Some("CS2")), 1.0);
+
+        assert_eq!(record.game.as_deref(), Some("CS2"));
+    }
+
+    #[test]
+    fn filter_by_game_and_limit() {
+        let records = vec![
+            new_record(MeasurementKind::Cm360, "tracked_turn", 9449, 30.0, None, &profile(Some("CS2")), 1.0),
+            new_record(MeasurementKind::Fov, "tracked_turn", 2000, 103.0, None, &profile(None), 1.0),
+            new_record(MeasurementKind::Fov, "tracked_turn", 2100, 104.0, None, &profile(Some("cs2")), 1.0),
+        ];
+        let filter = HistoryFilter {
+            game: Some("CS2".to_string()),
+            ..HistoryFilter::default()
+        };
+
+        let filtered = filter_history(&records, &filter);
+        assert_eq!(filtered.len(), 2);
+
+        let filter = HistoryFilter {
+            game: Some("CS2".to_string()),
+            limit: Some(1),
+            ..HistoryFilter::default()
+        };
+        assert_eq!(filter_history(&records, &filter)[0].counts, 2100);
+    }
+}
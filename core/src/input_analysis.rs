@@ -0,0 +1,230 @@
+use crate::models::{AccelerationReport, AccelerationSample, MousePacket, PollingReport};
+
+// Polling rates mice are usually sold with
+const NOMINAL_RATES: [u32; 7] = [125, 250, 500, 1000, 2000, 4000, 8000];
+
+// An interval counts as irregular when it is off by more than this fraction of the nominal interval
+const IRREGULAR_TOLERANCE: f64 = 0.5;
+
+// Spread of received/injected ratios between speeds above which the input path is treated as non-linear
+const ACCELERATION_TOLERANCE: f64 = 0.02;
+
+pub fn analyze_polling(packets: &[MousePacket]) -> PollingReport {
+    let mut report = PollingReport {
+        packets: packets.len(),
+        counts_distribution: counts_distribution(packets),
+        ..Default::default()
+    };
+
+    if packets.len() < 2 {
+        return report;
+    }
+
+    let intervals: Vec<u64> = packets
+        .windows(2)
+        .map(|pair| pair[1].time_us.saturating_sub(pair[0].time_us))
+        .collect();
+
+    let duration_us = packets[packets.len() - 1].time_us.saturating_sub(packets[0].time_us);
+    report.duration_ms = duration_us as f64 / 1000.0;
+
+    let mean = intervals.iter().sum::<u64>() as f64 / intervals.len() as f64;
+    let variance = intervals.iter().map(|&i| (i as f64 - mean).powi(2)).sum::<f64>() / intervals.len() as f64;
+    report.mean_interval_us = mean;
+    report.jitter_us = variance.sqrt();
+    report.min_interval_us = *intervals.iter().min().unwrap();
+    report.max_interval_us = *intervals.iter().max().unwrap();
+
+    if duration_us > 0 {
+        report.polling_rate = intervals.len() as f64 * 1_000_000.0 / duration_us as f64;
+    }
+
+    // Windows batches WM_INPUT messages, so single intervals are noisy. The median is a much
+    // better guess at the rate the mouse was configured for than the mean.
+    let mut sorted = intervals.clone();
+    sorted.sort_unstable();
+    let median = sorted[sorted.len() / 2].max(1) as f64;
+    report.nominal_rate = nearest_nominal_rate(1_000_000.0 / median);
+
+    let nominal_interval = 1_000_000.0 / report.nominal_rate as f64;
+    for &interval in &intervals {
+        let interval = interval as f64;
+        if (interval - nominal_interval).abs() > nominal_interval * IRREGULAR_TOLERANCE {
+            report.irregular_intervals += 1;
+        }
+        if interval > nominal_interval * (1.0 + IRREGULAR_TOLERANCE) {
+            report.dropped_packets += ((interval / nominal_interval).round() as usize).saturating_sub(1);
+        }
+    }
+
+    report
+}
+
+// Rates double at every step, so compare them on a log scale
+fn nearest_nominal_rate(rate: f64) -> u32 {
+    *NOMINAL_RATES
+        .iter()
+        .min_by(|a, b| {
+            let da = (rate / **a as f64).ln().abs();
+            let db = (rate / **b as f64).ln().abs();
+            da.total_cmp(&db)
+        })
+        .unwrap()
+}
+
+// Number of packets per count value, using the larger of both axes for each packet
+fn counts_distribution(packets: &[MousePacket]) -> Vec<(i32, usize)> {
+    let mut distribution: Vec<(i32, usize)> = Vec::new();
+
+    for packet in packets {
+        let counts = packet.dx.abs().max(packet.dy.abs());
+        match distribution.binary_search_by_key(&counts, |&(c, _)| c) {
+            Ok(index) => distribution[index].1 += 1,
+            Err(index) => distribution.insert(index, (counts, 1)),
+        }
+    }
+
+    distribution
+}
+
+// Takes (speed in counts/s, injected counts, pixels the cursor moved) for each run of the acceleration
+// test. Without acceleration the cursor moves the same distance at every speed.
+pub fn analyze_acceleration(runs: &[(f64, i32, i32)]) -> AccelerationReport {
+    let samples: Vec<AccelerationSample> = runs
+        .iter()
+        .map(|&(speed, expected, received)| AccelerationSample {
+            speed,
+            expected,
+            received,
+            ratio: if expected != 0 { received as f64 / expected as f64 } else { 0.0 },
+        })
+        .collect();
+
+    if samples.is_empty() {
+        return AccelerationReport {
+            samples,
+            scaling: 0.0,
+            deviation: 0.0,
+            acceleration_detected: false,
+            message: "No test runs recorded".to_string(),
+        };
+    }
+
+    let ratios: Vec<f64> = samples.iter().map(|s| s.ratio).collect();
+    let scaling = ratios.iter().sum::<f64>() / ratios.len() as f64;
+    let min = ratios.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = ratios.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let deviation = if scaling > 0.0 { (max - min) / scaling } else { 0.0 };
+
+    let acceleration_detected = deviation > ACCELERATION_TOLERANCE;
+    let message = if scaling == 0.0 {
+        "The cursor didn't move. Make sure nothing else controls the cursor during the test".to_string()
+    } else if acceleration_detected {
+        format!(
+            "Cursor movement varies by {:.1}% with speed. Mouse acceleration or \"Enhance pointer precision\" seems to be active",
+            deviation * 100.0
+        )
+    } else if (scaling - 1.0).abs() > ACCELERATION_TOLERANCE {
+        format!(
+            "No acceleration, but cursor movement is scaled by {:.3}. Check your pointer speed",
+            scaling
+        )
+    } else {
+        "No acceleration detected".to_string()
+    };
+
+    AccelerationReport {
+        samples,
+        scaling,
+        deviation,
+        acceleration_detected,
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packets(intervals: &[u64]) -> Vec<MousePacket> {
+        let mut time_us = 0;
+        let mut packets = vec![MousePacket { time_us, dx: 1, dy: 0 }];
+        for &interval in intervals {
+            time_us += interval;
+            packets.push(MousePacket { time_us, dx: 1, dy: 0 });
+        }
+        packets
+    }
+
+    #[test]
+    fn steady_polling_rate() {
+        let report = analyze_polling(&packets(&[1000; 100]));
+
+        assert_eq!(report.packets, 101);
+        assert!((report.duration_ms - 100.0).abs() < 1e-9);
+        assert!((report.polling_rate - 1000.0).abs() < 1e-9);
+        assert_eq!(report.nominal_rate, 1000);
+        assert_eq!(report.jitter_us, 0.0);
+        assert_eq!(report.irregular_intervals, 0);
+        assert_eq!(report.dropped_packets, 0);
+    }
+
+    #[test]
+    fn jitter_is_the_spread_of_the_intervals() {
+        let intervals: Vec<u64> = (0..100).map(|i| if i % 2 == 0 { 900 } else { 1100 }).collect();
+        let report = analyze_polling(&packets(&intervals));
+
+        assert!((report.mean_interval_us - 1000.0).abs() < 1e-9);
+        assert!((report.jitter_us - 100.0).abs() < 1e-9);
+        assert_eq!(report.min_interval_us, 900);
+        assert_eq!(report.max_interval_us, 1100);
+        assert_eq!(report.nominal_rate, 1000);
+        assert_eq!(report.irregular_intervals, 0);
+    }
+
+    #[test]
+    fn gaps_count_as_dropped_packets() {
+        let mut intervals = vec![2000; 50];
+        intervals[10] = 6000;
+        intervals[20] = 4000;
+        let report = analyze_polling(&packets(&intervals));
+
+        assert_eq!(report.nominal_rate, 500);
+        assert_eq!(report.irregular_intervals, 2);
+        assert_eq!(report.dropped_packets, 3);
+    }
+
+    #[test]
+    fn out_of_order_timestamps_do_not_panic() {
+        let packets = [
+            MousePacket { time_us: 5000, dx: 1, dy: 0 },
+            MousePacket { time_us: 6000, dx: 1, dy: 0 },
+            MousePacket { time_us: 1000, dx: 1, dy: 0 },
+        ];
+        let report = analyze_polling(&packets);
+
+        assert_eq!(report.duration_ms, 0.0);
+        assert_eq!(report.polling_rate, 0.0);
+    }
+
+    #[test]
+    fn too_few_packets_give_an_empty_report() {
+        let report = analyze_polling(&packets(&[]));
+
+        assert_eq!(report.packets, 1);
+        assert_eq!(report.polling_rate, 0.0);
+        assert_eq!(report.nominal_rate, 0);
+    }
+
+    #[test]
+    fn counts_distribution_uses_the_larger_axis() {
+        let packets = [
+            MousePacket { time_us: 0, dx: 2, dy: -3 },
+            MousePacket { time_us: 1000, dx: -3, dy: 1 },
+            MousePacket { time_us: 2000, dx: 1, dy: 0 },
+        ];
+        let report = analyze_polling(&packets);
+
+        assert_eq!(report.counts_distribution, vec![(1, 1), (3, 2)]);
+    }
+}
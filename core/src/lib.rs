@@ -0,0 +1,18 @@
+// Sensitivity math, the game list, profiles and measurement logic of AimCalibrate, without anything
+// tied to the app window or to Windows, so other tools can use it as well
+pub mod acceleration;
+pub mod batch;
+pub mod calculations;
+pub mod discovery;
+pub mod game_config;
+pub mod gamepad;
+pub mod history;
+pub mod input_analysis;
+pub mod measurement;
+pub mod models;
+pub mod optimizer;
+pub mod scopes;
+pub mod sens_finder;
+pub mod storage;
+pub mod traces;
+pub mod wizard;
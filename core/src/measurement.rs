@@ -0,0 +1,168 @@
+use crate::calculations::calculate_effective_dpi;
+use crate::models::{DpiStuff, YawStuff};
+
+// Yaw from a tracked 360 turn, searched in +-10% around it afterwards
+pub fn finish_yaw_measurement(yaw: &mut YawStuff, counts: i32) {
+    yaw.counts = counts.abs();
+    yaw.inc = 360.0 / yaw.counts as f64;
+    yaw.yaw = yaw.inc / yaw.sens;
+    yaw.lower_limit = yaw.yaw * 0.9;
+    yaw.upper_limit = yaw.yaw * 1.1;
+}
+
+pub fn set_yaw_sens(yaw: &mut YawStuff, sens: f64) {
+    yaw.sens = sens;
+    yaw.yaw = yaw.inc / yaw.sens;
+    yaw.lower_limit = yaw.yaw * 0.9;
+    yaw.upper_limit = yaw.yaw * 1.1;
+}
+
+fn bisect_yaw(yaw: &mut YawStuff) {
+    yaw.yaw = (yaw.upper_limit + yaw.lower_limit) / 2.0;
+    yaw.inc = yaw.sens * yaw.yaw;
+    yaw.counts = (360.0 / yaw.inc).round() as i32;
+}
+
+// The yaw is lower than the current guess
+pub fn lower_yaw_estimate(yaw: &mut YawStuff) {
+    yaw.upper_limit = yaw.yaw;
+    bisect_yaw(yaw);
+}
+
+// The yaw is higher than the current guess
+pub fn raise_yaw_estimate(yaw: &mut YawStuff) {
+    yaw.lower_limit = yaw.yaw;
+    bisect_yaw(yaw);
+}
+
+fn update_effective_dpi(dpi: &mut DpiStuff) {
+    let (effective_dpi, spread) = calculate_effective_dpi(&dpi.samples, dpi.distance);
+    dpi.effective_dpi = effective_dpi;
+    dpi.spread = spread;
+}
+
+pub fn add_dpi_sample(dpi: &mut DpiStuff, counts: i32) {
+    dpi.samples.push(counts.abs());
+    update_effective_dpi(dpi);
+}
+
+// Drops the last pass, e.g. when the mouse slipped off the ruler
+pub fn drop_dpi_sample(dpi: &mut DpiStuff) {
+    dpi.samples.pop();
+    update_effective_dpi(dpi);
+}
+
+pub fn set_dpi_measurement(dpi: &mut DpiStuff, nominal_dpi: i32, distance: f64) {
+    dpi.dpi = nominal_dpi;
+    dpi.distance = distance;
+    update_effective_dpi(dpi);
+}
+
+pub fn reset_dpi_measurement(dpi: &mut DpiStuff) {
+    dpi.samples.clear();
+    dpi.effective_dpi = 0.0;
+    dpi.spread = 0.0;
+}
+
+// Factor between the DPI the mouse reports and what it really does, None without a measurement
+pub fn measured_dpi_correction(dpi: &DpiStuff) -> Option<f64> {
+    if dpi.samples.is_empty() || dpi.dpi <= 0 {
+        None
+    } else {
+        Some(dpi.effective_dpi / dpi.dpi as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn yaw_stuff(sens: f64) -> YawStuff {
+        YawStuff {
+            sens,
+            counts: 0,
+            inc: 0.0,
+            yaw: 0.0,
+            lower_limit: 0.0,
+            upper_limit: 0.0,
+        }
+    }
+
+    fn dpi_stuff(dpi: i32, distance: f64) -> DpiStuff {
+        DpiStuff {
+            dpi,
+            distance,
+            samples: Vec::new(),
+            effective_dpi: 0.0,
+            spread: 0.0,
+        }
+    }
+
+    #[test]
+    fn a_full_turn_gives_the_yaw() {
+        let mut yaw = yaw_stuff(2.0);
+        finish_yaw_measurement(&mut yaw, -8182);
+
+        assert_eq!(yaw.counts, 8182);
+        assert!((yaw.yaw - 360.0 / 8182.0 / 2.0).abs() < 1e-12);
+        assert!((yaw.lower_limit - yaw.yaw * 0.9).abs() < 1e-12);
+        assert!((yaw.upper_limit - yaw.yaw * 1.1).abs() < 1e-12);
+    }
+
+    #[test]
+    fn corrections_bisect_the_range() {
+        let mut yaw = yaw_stuff(1.0);
+        finish_yaw_measurement(&mut yaw, 16364);
+        let measured = yaw.yaw;
+
+        lower_yaw_estimate(&mut yaw);
+        assert!((yaw.yaw - measured * 0.95).abs() < 1e-12);
+        assert_eq!(yaw.upper_limit, measured);
+        assert_eq!(yaw.counts, (360.0 / (measured * 0.95)).round() as i32);
+
+        raise_yaw_estimate(&mut yaw);
+        assert!((yaw.yaw - measured * 0.975).abs() < 1e-12);
+        assert!((yaw.lower_limit - measured * 0.95).abs() < 1e-12);
+    }
+
+    #[test]
+    fn changing_the_sens_keeps_the_turn() {
+        let mut yaw = yaw_stuff(1.0);
+        finish_yaw_measurement(&mut yaw, 16364);
+        let measured = yaw.yaw;
+
+        set_yaw_sens(&mut yaw, 2.0);
+        assert!((yaw.yaw - measured / 2.0).abs() < 1e-12);
+        assert!((yaw.upper_limit - yaw.yaw * 1.1).abs() < 1e-12);
+    }
+
+    #[test]
+    fn dpi_samples_average_and_drop() {
+        let mut dpi = dpi_stuff(800, 2.54 * 4.0);
+        assert_eq!(measured_dpi_correction(&dpi), None);
+
+        add_dpi_sample(&mut dpi, 3200);
+        add_dpi_sample(&mut dpi, -3360);
+        assert_eq!(dpi.samples, vec![3200, 3360]);
+        assert!((dpi.effective_dpi - 820.0).abs() < 1e-9);
+        assert!((dpi.spread - 20.0).abs() < 1e-9);
+        assert!((measured_dpi_correction(&dpi).unwrap() - 1.025).abs() < 1e-9);
+
+        drop_dpi_sample(&mut dpi);
+        assert!((dpi.effective_dpi - 800.0).abs() < 1e-9);
+        assert_eq!(dpi.spread, 0.0);
+
+        reset_dpi_measurement(&mut dpi);
+        assert_eq!(measured_dpi_correction(&dpi), None);
+    }
+
+    #[test]
+    fn new_distance_recomputes_the_dpi() {
+        let mut dpi = dpi_stuff(800, 2.54 * 4.0);
+        add_dpi_sample(&mut dpi, 3200);
+
+        set_dpi_measurement(&mut dpi, 1600, 2.54 * 2.0);
+        assert!((dpi.effective_dpi - 1600.0).abs() < 1e-9);
+        assert!((measured_dpi_correction(&dpi).unwrap() - 1.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,568 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct YawStuff {
+    pub sens: f64,
+    pub counts: i32,
+    pub inc: f64,
+    pub yaw: f64,
+    pub lower_limit: f64,
+    pub upper_limit: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GameYaw {
+    pub name: String,
+    pub yaw: f64,
+    // (sens, yaw) points for games whose yaw changes with the sensitivity
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub yaw_curve: Option<Vec<(f64, f64)>>,
+    #[serde(default = "default_raw_input")]
+    pub raw_input: bool,
+    #[serde(flatten, default)]
+    pub granularity: SensGranularity,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scopes: Vec<Scope>,
+    #[serde(default)]
+    pub scope_convention: ScopeConvention,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gamepad: Option<GamepadProfile>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config: Option<GameConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub steam_app_id: Option<u32>,
+}
+
+// The game list as last read from Games.json. While the file on disk has an error, `error`
+// describes it and `games` still holds the last good list.
+#[derive(Serialize, Clone, Default)]
+pub struct GameList {
+    pub games: Vec<GameYaw>,
+    pub error: Option<String>,
+}
+
+// Where a game stores its sensitivity
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GameConfig {
+    pub format: ConfigFormat,
+    pub path: String,
+    // Setting name, or a dotted path for JSON. Falls back to the format's usual name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigFormat {
+    // config.cfg with `sensitivity "2.5"` lines
+    Source,
+    // Quake 3 style cfg with `seta sensitivity "2.5"` lines
+    Quake,
+    // Unreal ini with `MouseSensitivity=2.5` lines
+    Unreal,
+    Json,
+    Xml,
+}
+
+#[derive(Serialize, Clone)]
+pub struct InstalledGame {
+    pub name: String,
+    pub app_id: Option<u32>,
+    pub install_dir: String,
+    // Steam library or user-defined folder the game was found in
+    pub library: String,
+    // Name of the Games.json entry it belongs to
+    pub matched: Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct GameConfigValues {
+    pub path: String,
+    pub sens: Option<f64>,
+    // Only formats that store the yaw next to the sensitivity, like m_yaw in Source
+    pub yaw: Option<f64>,
+}
+
+// How a game turns the view with an analog stick
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GamepadProfile {
+    // Turn rate in deg/s at full deflection per unit of in-game sensitivity
+    pub rate_per_sens: f64,
+    #[serde(default = "default_exponent")]
+    pub exponent: f64,
+    #[serde(default)]
+    pub deadzone: f64,
+    // Extra turn rate at full deflection, as a fraction of the max rate, reached after accel_time seconds
+    #[serde(default)]
+    pub accel_boost: f64,
+    #[serde(default)]
+    pub accel_time: f64,
+}
+
+fn default_exponent() -> f64 {
+    1.0
+}
+
+// Sensitivity multiplier as a function of input speed in counts/ms
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AccelCurve {
+    Linear {
+        accel: f64,
+        #[serde(default)]
+        offset: f64,
+        #[serde(default)]
+        cap: Option<f64>,
+    },
+    // Quake/Source style, the exponent is the power of the speed
+    Classic {
+        accel: f64,
+        exponent: f64,
+        #[serde(default)]
+        offset: f64,
+        #[serde(default)]
+        cap: Option<f64>,
+    },
+    Power {
+        scale: f64,
+        exponent: f64,
+        #[serde(default)]
+        output_offset: f64,
+        #[serde(default)]
+        cap: Option<f64>,
+    },
+    // Approaches the limit smoothly, decay sets how fast
+    Natural {
+        decay: f64,
+        limit: f64,
+        #[serde(default)]
+        offset: f64,
+    },
+}
+
+#[derive(Serialize, Clone)]
+pub struct AccelConversion {
+    pub sens: f64,
+    pub curve: AccelCurve,
+    // (speed in cm/s, cm/360 at that speed)
+    pub samples: Vec<(f64, f64)>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct GamepadConversion {
+    pub sens: f64,
+    pub turn_rate: f64,
+    pub cm_per360: f64,
+    // (deflection, source deg/s, destination deg/s)
+    pub samples: Vec<(f64, f64, f64)>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Scope {
+    pub name: String,
+    // Either the horizontal FOV while scoped in, or the zoom factor relative to hipfire
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fov: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zoom: Option<f64>,
+}
+
+// How the game expects scope sensitivity to be entered
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ScopeConvention {
+    // Multiplier of the hipfire sensitivity
+    #[default]
+    Multiplier,
+    // Separate sensitivity value on the same scale as hipfire
+    Absolute,
+    // Percentage of the hipfire speed, like Overwatch's relative aim sensitivity while zoomed
+    RelativeToFov,
+    // Monitor distance coefficient, like CoD's ADS coefficient
+    Coefficient,
+}
+
+#[derive(Serialize, Clone)]
+pub struct ScopeRow {
+    pub name: String,
+    pub fov: f64,
+    pub counts: i32,
+    pub cm_per360: f64,
+    pub multiplier: f64,
+    pub sens: f64,
+}
+
+#[derive(Serialize, Clone)]
+pub struct ScopeTable {
+    pub hipfire_sens: f64,
+    pub convention: ScopeConvention,
+    pub rows: Vec<ScopeRow>,
+}
+
+impl GameYaw {
+    pub fn unit(&self) -> SensUnit {
+        SensUnit::Game {
+            yaw: self.yaw,
+            yaw_curve: self.yaw_curve.clone(),
+            raw_input: self.raw_input,
+            granularity: self.granularity.clone(),
+        }
+    }
+}
+
+// Everything a sensitivity can be expressed in. Conversions go through cm/360.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SensUnit {
+    Game {
+        yaw: f64,
+        #[serde(default)]
+        yaw_curve: Option<Vec<(f64, f64)>>,
+        #[serde(default = "default_raw_input")]
+        raw_input: bool,
+        #[serde(default)]
+        granularity: SensGranularity,
+    },
+    Cm360,
+    InchesPer360,
+    DegPerCount,
+    CountsPer360,
+    DegPerMm,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct SensGranularity {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sens_step: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sens_min: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sens_max: Option<f64>,
+    // Below this the game starts to skip pixels or behaves badly, so optimizers avoid it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sens_floor: Option<f64>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct SensConversion {
+    pub sens: f64,
+    pub settable_sens: f64,
+    pub cm_per360: f64,
+    pub error_percent: f64,
+    pub suggested_dpi: Option<i32>,
+}
+
+fn default_raw_input() -> bool {
+    true
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct UserSettings {
+    pub cm360: f64,
+    pub dpi: i32,
+    pub normal_fov: f64,
+    pub scoped_fov: f64,
+    pub game_sens: f64,
+    pub game_fov: f64,
+    // Game the measurements are taken in, recorded with them in the history
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub game: Option<String>,
+}
+
+// State of the sens finder. Each round offers a faster and a slower cm/360 from the current range,
+// the chosen one keeps its half of the range.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SensFinder {
+    pub lower_limit: f64,
+    pub upper_limit: f64,
+    pub yaw: f64,
+    pub dpi: i32,
+    pub round: u32,
+    pub rounds: u32,
+    // Index into candidates of the one being tried right now
+    pub active: usize,
+    pub candidates: [f64; 2],
+    pub game_sens: [f64; 2],
+    pub choices: Vec<SensChoice>,
+    pub result: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SensChoice {
+    pub round: u32,
+    pub candidates: [f64; 2],
+    pub chosen: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DpiStuff {
+    pub dpi: i32,
+    pub distance: f64,
+    pub samples: Vec<i32>,
+    pub effective_dpi: f64,
+    pub spread: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AppSettings {
+    pub turn_speed: f32,
+    pub hotkeys: Vec<String>,
+    #[serde(default = "default_dpi_correction")]
+    pub dpi_correction: f64,
+    #[serde(default = "default_pointer_speed")]
+    pub pointer_speed: u32,
+    // Folders searched for games next to the Steam libraries
+    #[serde(default)]
+    pub game_folders: Vec<String>,
+    // Local control API, off unless turned on in the settings
+    #[serde(default)]
+    pub api_enabled: bool,
+    #[serde(default = "default_api_port")]
+    pub api_port: u16,
+    #[serde(default)]
+    pub api_token: String,
+}
+
+fn default_dpi_correction() -> f64 {
+    1.0
+}
+
+fn default_pointer_speed() -> u32 {
+    10
+}
+
+fn default_api_port() -> u16 {
+    43110
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        AppSettings {
+            turn_speed: 1.0,
+            hotkeys: vec!["F1".to_string(), "F2".to_string(), "F3".to_string(), "F4".to_string()],
+            dpi_correction: default_dpi_correction(),
+            pointer_speed: default_pointer_speed(),
+            game_folders: Vec::new(),
+            api_enabled: false,
+            api_port: default_api_port(),
+            api_token: String::new(),
+        }
+    }
+}
+
+// A settings or game file that didn't parse. It was renamed to `quarantined` and replaced with
+// `restored_from` if a good backup existed.
+#[derive(Serialize, Clone)]
+pub struct StorageIssue {
+    pub path: String,
+    pub error: String,
+    pub quarantined: String,
+    pub restored_from: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct MousePacket {
+    pub time_us: u64,
+    pub dx: i32,
+    pub dy: i32,
+}
+
+#[derive(Serialize, Clone, Default)]
+pub struct PollingReport {
+    pub packets: usize,
+    pub duration_ms: f64,
+    pub polling_rate: f64,
+    pub nominal_rate: u32,
+    pub mean_interval_us: f64,
+    pub jitter_us: f64,
+    pub min_interval_us: u64,
+    pub max_interval_us: u64,
+    pub irregular_intervals: usize,
+    pub dropped_packets: usize,
+    pub counts_distribution: Vec<(i32, usize)>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum FovScale {
+    #[serde(rename = "horizontal")]
+    Horizontal,
+    #[serde(rename = "4ml3")]
+    Hor4ML3,
+    #[serde(rename = "vertical")]
+    Vertical,
+}
+
+#[derive(Serialize, Clone)]
+pub struct AngularResolution {
+    pub deg_per_count: f64,
+    pub horizontal_fov: f64,
+    pub pixels_per_degree: f64,
+    pub pixels_per_count: f64,
+    pub counts_per_pixel: f64,
+    pub skips_pixels: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub enum TableFormat {
+    #[serde(rename = "csv")]
+    Csv,
+    #[serde(rename = "markdown")]
+    Markdown,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GameSensResult {
+    pub name: String,
+    pub sens: f64,
+    pub settable_sens: f64,
+    pub cm_per360: f64,
+    pub error_percent: f64,
+    pub below_floor: bool,
+}
+
+#[derive(Serialize, Clone)]
+pub struct DpiCandidate {
+    pub dpi: i32,
+    pub worst_error_percent: f64,
+    pub mean_error_percent: f64,
+    pub below_floor: bool,
+    pub games: Vec<GameSensResult>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct AccelerationSample {
+    pub speed: f64,
+    // Injected counts and the pixels the cursor moved for them
+    pub expected: i32,
+    pub received: i32,
+    pub ratio: f64,
+}
+
+#[derive(Serialize, Clone)]
+pub struct AccelerationReport {
+    pub samples: Vec<AccelerationSample>,
+    pub scaling: f64,
+    pub deviation: f64,
+    pub acceleration_detected: bool,
+    pub message: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MovementTrace {
+    pub version: u32,
+    pub recorded_at: u64,
+    pub device: Option<String>,
+    pub page: String,
+    pub settings: UserSettings,
+    pub dpi_correction: f64,
+    pub yaw_sens: f64,
+    // (time_us, dx, dy) per packet, kept as tuples so the file stays small
+    pub packets: Vec<(u64, i32, i32)>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct TraceReplay {
+    pub counts: i32,
+    pub cm_per360: f64,
+    pub fov16: f64,
+    pub yaw: f64,
+    pub polling: PollingReport,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct FovUpdatePayload {
+    pub fov16: f64,
+}
+
+// What a history entry measured. Yaw entries are also written when a measured yaw is saved to a game.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MeasurementKind {
+    Cm360,
+    Fov,
+    Yaw,
+}
+
+// One completed measurement in the history log. `profile` holds the cm/360, DPI and FOV values
+// that were set when it was taken, `counts` the mouse counts it was computed from.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MeasurementRecord {
+    pub timestamp: u64,
+    pub kind: MeasurementKind,
+    pub method: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub game: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    pub counts: i32,
+    pub value: f64,
+    pub profile: UserSettings,
+    pub dpi_correction: f64,
+}
+
+// Missing fields don't filter. `limit` keeps the newest entries.
+#[derive(Deserialize, Default)]
+pub struct HistoryFilter {
+    pub kind: Option<MeasurementKind>,
+    pub game: Option<String>,
+    pub device: Option<String>,
+    pub since: Option<u64>,
+    pub until: Option<u64>,
+    pub limit: Option<usize>,
+}
+
+// Where the calibration wizard is. The scope step repeats for every scope in the plan.
+#[derive(Serialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WizardStep {
+    #[default]
+    Idle,
+    MeasureYaw,
+    MeasureHipfireFov,
+    MeasureScopeFov,
+    Done,
+}
+
+// What the calibration wizard sets up. All turns are made at `sens`, the game's hipfire sensitivity.
+// The sensitivities at the end are calculated for `cm_per360` at `dpi`.
+#[derive(Deserialize, Clone)]
+pub struct CalibrationPlan {
+    pub game: String,
+    pub sens: f64,
+    pub cm_per360: f64,
+    pub dpi: i32,
+    #[serde(default = "default_raw_input")]
+    pub raw_input: bool,
+    #[serde(default)]
+    pub scope_convention: ScopeConvention,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    #[serde(default)]
+    pub monitor_distance: f64,
+}
+
+// State of the calibration wizard. `pending` holds the result of the current step until it is
+// confirmed, `yaw` is the yaw of the turns made, before the pointer speed is taken out.
+#[derive(Serialize, Clone, Default)]
+pub struct CalibrationWizard {
+    pub step: WizardStep,
+    pub game: String,
+    pub sens: f64,
+    pub cm_per360: f64,
+    pub dpi: i32,
+    pub raw_input: bool,
+    pub scope_convention: ScopeConvention,
+    pub monitor_distance: f64,
+    pub scopes: Vec<Scope>,
+    // Index into scopes of the one being measured
+    pub scope_index: usize,
+    pub yaw: Option<f64>,
+    pub hipfire_fov: Option<f64>,
+    pub pending: Option<f64>,
+    pub table: Option<ScopeTable>,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct CmUpdatePayload {
+    pub cm_per360: f64,
+}
@@ -0,0 +1,112 @@
+use crate::calculations::{cm_per360_to_unit, nearest_settable_sens, unit_to_cm_per360};
+use crate::models::{DpiCandidate, GameSensResult, GameYaw};
+
+pub fn game_sens_result(game: &GameYaw, cm_per360: f64, dpi: i32, dpi_correction: f64, pointer_speed: u32) -> GameSensResult {
+    let unit = game.unit();
+    let sens = cm_per360_to_unit(&unit, cm_per360, dpi, dpi_correction, pointer_speed);
+    let settable_sens = nearest_settable_sens(sens, &game.granularity);
+    let actual_cm = unit_to_cm_per360(&unit, settable_sens, dpi, dpi_correction, pointer_speed);
+
+    GameSensResult {
+        name: game.name.clone(),
+        sens,
+        settable_sens,
+        cm_per360: actual_cm,
+        error_percent: (actual_cm - cm_per360) / cm_per360 * 100.0,
+        below_floor: game.granularity.sens_floor.is_some_and(|floor| settable_sens < floor),
+    }
+}
+
+// Ranks every DPI step by the worst cm/360 error it leaves across the games, DPIs that push a game
+// below its sensitivity floor always come last
+pub fn optimize_dpi(
+    cm_per360: f64,
+    dpi_steps: &[i32],
+    games: &[GameYaw],
+    dpi_correction: f64,
+    pointer_speed: u32,
+) -> Result<Vec<DpiCandidate>, String> {
+    if !cm_per360.is_finite() || cm_per360 <= 0.0 {
+        return Err("cm/360 must be greater than zero".to_string());
+    }
+
+    let mut candidates: Vec<DpiCandidate> = dpi_steps
+        .iter()
+        .filter(|&&dpi| dpi > 0)
+        .map(|&dpi| {
+            let results: Vec<GameSensResult> = games
+                .iter()
+                .map(|game| game_sens_result(game, cm_per360, dpi, dpi_correction, pointer_speed))
+                .collect();
+
+            let worst_error_percent = results.iter().map(|r| r.error_percent.abs()).fold(0.0, f64::max);
+            let mean_error_percent = if results.is_empty() {
+                0.0
+            } else {
+                results.iter().map(|r| r.error_percent.abs()).sum::<f64>() / results.len() as f64
+            };
+
+            DpiCandidate {
+                dpi,
+                worst_error_percent,
+                mean_error_percent,
+                below_floor: results.iter().any(|r| r.below_floor),
+                games: results,
+            }
+        })
+        .collect();
+
+    candidates.sort_by(|a, b| {
+        a.below_floor
+            .cmp(&b.below_floor)
+            .then(a.worst_error_percent.total_cmp(&b.worst_error_percent))
+            .then(a.mean_error_percent.total_cmp(&b.mean_error_percent))
+    });
+
+    Ok(candidates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ScopeConvention, SensGranularity};
+
+    fn game(name: &str, yaw: f64, sens_step: f64) -> GameYaw {
+        GameYaw {
+            name: name.to_string(),
+            yaw,
+            yaw_curve: None,
+            raw_input: true,
+            granularity: SensGranularity {
+                sens_step: Some(sens_step),
+                ..SensGranularity::default()
+            },
+            scopes: Vec::new(),
+            scope_convention: ScopeConvention::default(),
+            gamepad: None,
+            config: None,
+            steam_app_id: None,
+        }
+    }
+
+    #[test]
+    fn rejects_zero_cm_per360() {
+        let games = [game("Counter-Strike 2", 0.022, 0.01)];
+
+        assert!(optimize_dpi(0.0, &[400, 800], &games, 1.0, 10).is_err());
+        assert!(optimize_dpi(f64::NAN, &[400, 800], &games, 1.0, 10).is_err());
+    }
+
+    #[test]
+    fn ranks_the_exact_match_first() {
+        // Sens 1.0 at 800 DPI, 1200 DPI would need 0.667 which the 0.5 steps can't hit
+        let games = [game("Counter-Strike 2", 0.022, 0.5)];
+        let cm_per360 = 360.0 / 0.022 / 800.0 * 2.54;
+
+        let candidates = optimize_dpi(cm_per360, &[1200, 800, 0], &games, 1.0, 10).unwrap();
+
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(candidates[0].dpi, 800);
+        assert!(candidates[0].worst_error_percent < 1e-9);
+    }
+}
@@ -0,0 +1,323 @@
+use crate::calculations::{calculate_counts, cm_per360_to_unit};
+use crate::models::{GameYaw, Scope, ScopeConvention, ScopeRow, ScopeTable};
+
+// Highest coefficient searched for by the coefficient model, CoD's slider ends at 2
+const MAX_COEFFICIENT: f64 = 10.0;
+// Screen width over height, used where a convention depends on the vertical FOV
+pub const DEFAULT_ASPECT_RATIO: f64 = 16.0 / 9.0;
+
+// FOVs are horizontal
+pub struct ScopeContext {
+    pub hipfire_fov: f64,
+    pub scoped_fov: f64,
+    pub hipfire_sens: f64,
+    pub aspect_ratio: f64,
+}
+
+// A way a game lets you enter scope sensitivity. The ratio is how fast the scope turns
+// per count compared to hipfire.
+pub trait ScopeSensModel {
+    // In-game value that makes the scope turn at the given ratio
+    fn scope_value(&self, ratio: f64, context: &ScopeContext) -> f64;
+
+    // Ratio an in-game value results in
+    fn scope_ratio(&self, value: f64, context: &ScopeContext) -> f64;
+}
+
+pub struct HipfireMultiplier;
+
+impl ScopeSensModel for HipfireMultiplier {
+    fn scope_value(&self, ratio: f64, _context: &ScopeContext) -> f64 {
+        ratio
+    }
+
+    fn scope_ratio(&self, value: f64, _context: &ScopeContext) -> f64 {
+        value
+    }
+}
+
+pub struct AbsoluteSens;
+
+impl ScopeSensModel for AbsoluteSens {
+    fn scope_value(&self, ratio: f64, context: &ScopeContext) -> f64 {
+        context.hipfire_sens * ratio
+    }
+
+    fn scope_ratio(&self, value: f64, context: &ScopeContext) -> f64 {
+        value / context.hipfire_sens
+    }
+}
+
+pub struct RelativeToFov {
+    // Value of the slider that keeps the hipfire degrees per count, 100 for a percentage
+    pub scale: f64,
+}
+
+impl ScopeSensModel for RelativeToFov {
+    fn scope_value(&self, ratio: f64, _context: &ScopeContext) -> f64 {
+        ratio * self.scale
+    }
+
+    fn scope_ratio(&self, value: f64, _context: &ScopeContext) -> f64 {
+        value / self.scale
+    }
+}
+
+// The coefficient is a distance from the center in half screen heights, so 1.33 matches the edge of
+// a 4:3 screen and 1.78 the edge of a 16:9 one
+pub struct MonitorDistanceCoefficient;
+
+impl ScopeSensModel for MonitorDistanceCoefficient {
+    // The ratio grows with the coefficient from focal length scaling at 0, so search for it
+    fn scope_value(&self, ratio: f64, context: &ScopeContext) -> f64 {
+        if ratio <= self.scope_ratio(0.0, context) {
+            return 0.0;
+        }
+
+        let mut low = 0.0;
+        let mut high = MAX_COEFFICIENT;
+        for _ in 0..100 {
+            let mid = (low + high) / 2.0;
+            if self.scope_ratio(mid, context) < ratio {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        (low + high) / 2.0
+    }
+
+    fn scope_ratio(&self, value: f64, context: &ScopeContext) -> f64 {
+        monitor_distance_ratio(context.hipfire_fov, context.scoped_fov, value / context.aspect_ratio)
+    }
+}
+
+pub fn scope_model(convention: ScopeConvention) -> Box<dyn ScopeSensModel> {
+    match convention {
+        ScopeConvention::Multiplier => Box::new(HipfireMultiplier),
+        ScopeConvention::Absolute => Box::new(AbsoluteSens),
+        ScopeConvention::RelativeToFov => Box::new(RelativeToFov { scale: 100.0 }),
+        ScopeConvention::Coefficient => Box::new(MonitorDistanceCoefficient),
+    }
+}
+
+// FOV of a scope that magnifies the hipfire view by the given zoom factor
+pub fn scope_fov_from_zoom(hipfire_fov: f64, zoom: f64) -> f64 {
+    let half_hipfire = (hipfire_fov / 2.0).to_radians();
+    ((half_hipfire.tan() / zoom).atan() * 2.0).to_degrees()
+}
+
+pub fn scope_fov(scope: &Scope, hipfire_fov: f64) -> Option<f64> {
+    match (scope.fov, scope.zoom) {
+        (Some(fov), _) => Some(fov),
+        (None, Some(zoom)) if zoom > 0.0 => Some(scope_fov_from_zoom(hipfire_fov, zoom)),
+        _ => None,
+    }
+}
+
+// Focal length scaling: the scoped view turns slower by the ratio of the zoomed to the hipfire focal length
+pub fn focal_length_multiplier(hipfire_fov: f64, scoped_fov: f64) -> f64 {
+    (scoped_fov / 2.0).to_radians().tan() / (hipfire_fov / 2.0).to_radians().tan()
+}
+
+// Ratio that matches the movement to a point at the given fraction of the half screen width.
+// 0 is focal length scaling, 1 matches the edge of the screen.
+pub fn monitor_distance_ratio(hipfire_fov: f64, scoped_fov: f64, monitor_distance: f64) -> f64 {
+    if monitor_distance <= 0.0 {
+        return focal_length_multiplier(hipfire_fov, scoped_fov);
+    }
+
+    let scoped = (monitor_distance * (scoped_fov / 2.0).to_radians().tan()).atan();
+    let hipfire = (monitor_distance * (hipfire_fov / 2.0).to_radians().tan()).atan();
+    scoped / hipfire
+}
+
+// Scopes without a FOV between 0 and 180 degrees are left out of the table
+pub fn calculate_scope_table(
+    game: &GameYaw,
+    cm_per360: f64,
+    dpi: i32,
+    hipfire_fov: f64,
+    monitor_distance: f64,
+    dpi_correction: f64,
+    pointer_speed: u32,
+) -> Result<ScopeTable, String> {
+    if !cm_per360.is_finite() || cm_per360 <= 0.0 {
+        return Err("cm/360 must be greater than zero".to_string());
+    }
+    if dpi <= 0 {
+        return Err("DPI must be greater than zero".to_string());
+    }
+    if !valid_fov(hipfire_fov) {
+        return Err("Hipfire FOV must be between 0 and 180 degrees".to_string());
+    }
+
+    let hipfire_sens = cm_per360_to_unit(&game.unit(), cm_per360, dpi, dpi_correction, pointer_speed);
+    if !hipfire_sens.is_finite() || hipfire_sens <= 0.0 {
+        return Err(format!("{} has no valid sensitivity, check its yaw", game.name));
+    }
+    let model = scope_model(game.scope_convention);
+
+    let rows = game
+        .scopes
+        .iter()
+        .filter_map(|scope| {
+            let fov = scope_fov(scope, hipfire_fov).filter(|&fov| valid_fov(fov))?;
+            let multiplier = monitor_distance_ratio(hipfire_fov, fov, monitor_distance);
+            let context = ScopeContext {
+                hipfire_fov,
+                scoped_fov: fov,
+                hipfire_sens,
+                aspect_ratio: DEFAULT_ASPECT_RATIO,
+            };
+
+            Some(ScopeRow {
+                name: scope.name.clone(),
+                fov,
+                counts: calculate_counts(cm_per360 / multiplier, dpi, dpi_correction),
+                cm_per360: cm_per360 / multiplier,
+                multiplier,
+                sens: model.scope_value(multiplier, &context),
+            })
+        })
+        .collect();
+
+    Ok(ScopeTable {
+        hipfire_sens,
+        convention: game.scope_convention,
+        rows,
+    })
+}
+
+fn valid_fov(fov: f64) -> bool {
+    fov.is_finite() && fov > 0.0 && fov < 180.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SensGranularity;
+
+    // Overwatch's hipfire FOV and Widowmaker's zoomed FOV
+    const HIPFIRE_FOV: f64 = 103.0;
+    const WIDOW_FOV: f64 = 51.0;
+
+    fn context() -> ScopeContext {
+        ScopeContext {
+            hipfire_fov: HIPFIRE_FOV,
+            scoped_fov: WIDOW_FOV,
+            hipfire_sens: 4.0,
+            aspect_ratio: DEFAULT_ASPECT_RATIO,
+        }
+    }
+
+    #[test]
+    fn relative_aim_keeps_hipfire_at_100() {
+        let model = scope_model(ScopeConvention::RelativeToFov);
+
+        assert!((model.scope_value(1.0, &context()) - 100.0).abs() < 1e-9);
+        assert!((model.scope_ratio(50.0, &context()) - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn relative_aim_for_focal_length_scaling() {
+        let ratio = focal_length_multiplier(HIPFIRE_FOV, WIDOW_FOV);
+        let value = scope_model(ScopeConvention::RelativeToFov).scope_value(ratio, &context());
+
+        assert!((value - 37.89).abs() < 0.1, "{}", value);
+    }
+
+    #[test]
+    fn coefficient_is_measured_in_half_screen_heights() {
+        let model = scope_model(ScopeConvention::Coefficient);
+        let edge_16_9 = monitor_distance_ratio(HIPFIRE_FOV, WIDOW_FOV, 1.0);
+        let edge_4_3 = monitor_distance_ratio(HIPFIRE_FOV, WIDOW_FOV, 0.75);
+
+        assert!((model.scope_value(edge_16_9, &context()) - 16.0 / 9.0).abs() < 1e-6);
+        assert!((model.scope_value(edge_4_3, &context()) - 4.0 / 3.0).abs() < 1e-6);
+        assert!((model.scope_ratio(16.0 / 9.0, &context()) - edge_16_9).abs() < 1e-9);
+    }
+
+    #[test]
+    fn coefficient_zero_is_focal_length_scaling() {
+        let model = scope_model(ScopeConvention::Coefficient);
+        let focal = focal_length_multiplier(HIPFIRE_FOV, WIDOW_FOV);
+
+        assert!((model.scope_ratio(0.0, &context()) - focal).abs() < 1e-12);
+        assert_eq!(model.scope_value(focal * 0.9, &context()), 0.0);
+    }
+
+    #[test]
+    fn multiplier_and_absolute_scale_the_hipfire_sens() {
+        assert_eq!(scope_model(ScopeConvention::Multiplier).scope_value(0.5, &context()), 0.5);
+        assert_eq!(scope_model(ScopeConvention::Absolute).scope_value(0.5, &context()), 2.0);
+        assert_eq!(scope_model(ScopeConvention::Absolute).scope_ratio(2.0, &context()), 0.5);
+    }
+
+    fn game(convention: ScopeConvention, scopes: Vec<Scope>) -> GameYaw {
+        GameYaw {
+            name: "Overwatch".to_string(),
+            yaw: 0.0066,
+            yaw_curve: None,
+            raw_input: true,
+            granularity: SensGranularity::default(),
+            scopes,
+            scope_convention: convention,
+            gamepad: None,
+            config: None,
+            steam_app_id: None,
+        }
+    }
+
+    fn scopes() -> Vec<Scope> {
+        vec![
+            Scope { name: "Widow".to_string(), fov: Some(WIDOW_FOV), zoom: None },
+            Scope { name: "2x".to_string(), fov: None, zoom: Some(2.0) },
+            Scope { name: "Broken".to_string(), fov: Some(0.0), zoom: None },
+            Scope { name: "Empty".to_string(), fov: None, zoom: None },
+        ]
+    }
+
+    #[test]
+    fn focal_length_table() {
+        let game = game(ScopeConvention::Multiplier, scopes());
+        let table = calculate_scope_table(&game, 30.0, 800, HIPFIRE_FOV, 0.0, 1.0, 10).unwrap();
+
+        assert_eq!(table.rows.len(), 2);
+        let widow = &table.rows[0];
+        let focal = focal_length_multiplier(HIPFIRE_FOV, WIDOW_FOV);
+        assert!((widow.multiplier - focal).abs() < 1e-12);
+        assert!((widow.sens - focal).abs() < 1e-12);
+        assert!((widow.cm_per360 - 30.0 / focal).abs() < 1e-9);
+        assert_eq!(widow.counts, calculate_counts(30.0 / focal, 800, 1.0));
+
+        // A 2x zoom halves the focal length ratio, so a 360 takes twice the distance
+        let zoomed = &table.rows[1];
+        assert!((zoomed.multiplier - 0.5).abs() < 1e-12);
+        assert!((zoomed.cm_per360 - 60.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn focal_length_table_in_relative_aim() {
+        let game = game(ScopeConvention::RelativeToFov, scopes());
+        let table = calculate_scope_table(&game, 30.0, 800, HIPFIRE_FOV, 0.0, 1.0, 10).unwrap();
+
+        assert!((table.rows[0].sens - 37.89).abs() < 0.1, "{}", table.rows[0].sens);
+        assert!((table.rows[1].sens - 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn scope_table_rejects_invalid_input() {
+        let overwatch = game(ScopeConvention::Multiplier, scopes());
+
+        assert!(calculate_scope_table(&overwatch, 0.0, 800, HIPFIRE_FOV, 0.0, 1.0, 10).is_err());
+        assert!(calculate_scope_table(&overwatch, f64::INFINITY, 800, HIPFIRE_FOV, 0.0, 1.0, 10).is_err());
+        assert!(calculate_scope_table(&overwatch, 30.0, 0, HIPFIRE_FOV, 0.0, 1.0, 10).is_err());
+        assert!(calculate_scope_table(&overwatch, 30.0, 800, 0.0, 0.0, 1.0, 10).is_err());
+        assert!(calculate_scope_table(&overwatch, 30.0, 800, 180.0, 0.0, 1.0, 10).is_err());
+        assert!(calculate_scope_table(&overwatch, 30.0, 800, f64::NAN, 0.0, 1.0, 10).is_err());
+
+        let unmeasured = GameYaw { yaw: 0.0, ..game(ScopeConvention::Multiplier, scopes()) };
+        assert!(calculate_scope_table(&unmeasured, 30.0, 800, HIPFIRE_FOV, 0.0, 1.0, 10).is_err());
+    }
+}
@@ -0,0 +1,155 @@
+use crate::calculations::calculate_sens;
+use crate::models::{SensChoice, SensFinder};
+
+pub const SENS_FINDER_ROUNDS: u32 = 7;
+
+// Candidates sit at a quarter and three quarters of the range. The range is split on a log scale,
+// so a step from 20 to 40 cm/360 feels as big as one from 40 to 80.
+fn sens_candidates(lower_limit: f64, upper_limit: f64) -> [f64; 2] {
+    let ratio = upper_limit / lower_limit;
+    [lower_limit * ratio.powf(0.25), lower_limit * ratio.powf(0.75)]
+}
+
+fn geometric_mean(a: f64, b: f64) -> f64 {
+    (a * b).sqrt()
+}
+
+fn update_candidates(finder: &mut SensFinder, dpi_correction: f64) {
+    finder.candidates = sens_candidates(finder.lower_limit, finder.upper_limit);
+    finder.game_sens = finder
+        .candidates
+        .map(|cm_per360| calculate_sens(cm_per360, finder.dpi, finder.yaw, dpi_correction));
+    finder.active = 0;
+}
+
+pub fn new_sens_finder(lower_limit: f64, upper_limit: f64, yaw: f64, dpi: i32, dpi_correction: f64) -> SensFinder {
+    let mut finder = SensFinder {
+        lower_limit: lower_limit.min(upper_limit),
+        upper_limit: upper_limit.max(lower_limit),
+        yaw,
+        dpi,
+        round: 0,
+        rounds: SENS_FINDER_ROUNDS,
+        active: 0,
+        candidates: [0.0; 2],
+        game_sens: [0.0; 2],
+        choices: Vec::new(),
+        result: None,
+    };
+    update_candidates(&mut finder, dpi_correction);
+    finder
+}
+
+// Keeps the half of the range the chosen candidate is in. After the last round the middle of
+// what is left is the result.
+pub fn choose_candidate(finder: &mut SensFinder, choice: usize, dpi_correction: f64) {
+    if finder.result.is_some() || choice > 1 {
+        return;
+    }
+
+    let middle = geometric_mean(finder.lower_limit, finder.upper_limit);
+    if choice == 0 {
+        finder.upper_limit = middle;
+    } else {
+        finder.lower_limit = middle;
+    }
+
+    finder.round += 1;
+    finder.choices.push(SensChoice {
+        round: finder.round,
+        candidates: finder.candidates,
+        chosen: finder.candidates[choice],
+    });
+
+    if finder.round >= finder.rounds {
+        finder.result = Some(geometric_mean(finder.lower_limit, finder.upper_limit));
+    } else {
+        update_candidates(finder, dpi_correction);
+    }
+}
+
+// Goes back one round, e.g. after pressing the wrong hotkey
+pub fn undo_choice(finder: &mut SensFinder, dpi_correction: f64) {
+    if let Some(choice) = finder.choices.pop() {
+        let ratio = choice.candidates[1] / choice.candidates[0];
+        // The candidates were a quarter of the range in from each end, so they span half of it
+        finder.lower_limit = choice.candidates[0] / ratio.sqrt();
+        finder.upper_limit = choice.candidates[1] * ratio.sqrt();
+        finder.round -= 1;
+        finder.result = None;
+        update_candidates(finder, dpi_correction);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{} != {}", actual, expected);
+    }
+
+    #[test]
+    fn candidates_split_the_range_on_a_log_scale() {
+        let finder = new_sens_finder(80.0, 20.0, 0.022, 800, 1.0);
+
+        assert_eq!((finder.lower_limit, finder.upper_limit), (20.0, 80.0));
+        assert_close(finder.candidates[0], 20.0 * 2f64.sqrt());
+        assert_close(finder.candidates[1], 40.0 * 2f64.sqrt());
+        assert_close(finder.game_sens[0], calculate_sens(finder.candidates[0], 800, 0.022, 1.0));
+    }
+
+    #[test]
+    fn choices_keep_their_half() {
+        let mut finder = new_sens_finder(20.0, 80.0, 0.022, 800, 1.0);
+
+        choose_candidate(&mut finder, 0, 1.0);
+        assert_close(finder.upper_limit, 40.0);
+        choose_candidate(&mut finder, 1, 1.0);
+        assert_close(finder.lower_limit, 20.0 * 2f64.sqrt());
+
+        assert_eq!(finder.round, 2);
+        assert_eq!(finder.choices.len(), 2);
+        assert_eq!(finder.result, None);
+
+        // Out of range choices are ignored
+        choose_candidate(&mut finder, 2, 1.0);
+        assert_eq!(finder.round, 2);
+    }
+
+    #[test]
+    fn last_round_gives_the_result() {
+        let mut finder = new_sens_finder(20.0, 80.0, 0.022, 800, 1.0);
+        for _ in 0..SENS_FINDER_ROUNDS {
+            choose_candidate(&mut finder, 1, 1.0);
+        }
+
+        let result = finder.result.unwrap();
+        assert_close(result, (finder.lower_limit * finder.upper_limit).sqrt());
+        assert!(result > 75.0 && result < 80.0);
+
+        choose_candidate(&mut finder, 0, 1.0);
+        assert_eq!(finder.choices.len(), SENS_FINDER_ROUNDS as usize);
+    }
+
+    #[test]
+    fn undo_restores_the_previous_round() {
+        let mut finder = new_sens_finder(20.0, 80.0, 0.022, 800, 1.0);
+        choose_candidate(&mut finder, 0, 1.0);
+        choose_candidate(&mut finder, 1, 1.0);
+
+        undo_choice(&mut finder, 1.0);
+        assert_eq!(finder.round, 1);
+        assert_close(finder.lower_limit, 20.0);
+        assert_close(finder.upper_limit, 40.0);
+
+        undo_choice(&mut finder, 1.0);
+        assert_eq!(finder.round, 0);
+        assert_close(finder.lower_limit, 20.0);
+        assert_close(finder.upper_limit, 80.0);
+
+        // Nothing left to undo
+        undo_choice(&mut finder, 1.0);
+        assert_eq!(finder.round, 0);
+    }
+}
@@ -0,0 +1,606 @@
+use crate::models::{AppSettings, GameYaw, MeasurementRecord, MovementTrace, StorageIssue};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Older versions kept next to settings.json and Games.json
+pub const BACKUP_COUNT: usize = 3;
+
+pub fn settings_path(config_dir: &Path) -> PathBuf {
+    config_dir.join("settings.json")
+}
+
+pub fn games_path(config_dir: &Path) -> PathBuf {
+    config_dir.join("Games.json")
+}
+
+pub fn history_path(config_dir: &Path) -> PathBuf {
+    config_dir.join("history.jsonl")
+}
+
+pub fn traces_dir(config_dir: &Path) -> PathBuf {
+    config_dir.join("traces")
+}
+
+pub fn trace_path(traces_dir: &Path, name: &str) -> PathBuf {
+    let file_name: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+
+    traces_dir.join(format!("{}.trace", file_name))
+}
+
+// Set to a folder to keep settings, the game list and traces there instead of the default location
+pub const CONFIG_DIR_ENV: &str = "AIMCALIBRATE_CONFIG_DIR";
+// A file with this name next to the executable turns on portable mode
+pub const PORTABLE_MARKER: &str = "portable";
+
+// The folder all data is stored in. It is resolved once at startup and then passed to everything
+// that reads or writes files. The folders are only created by the first write, so reading from the
+// command line never leaves an empty config folder behind.
+#[derive(Serialize, Clone)]
+pub struct StorageContext {
+    pub config_dir: PathBuf,
+    pub portable: bool,
+}
+
+impl StorageContext {
+    pub fn new(config_dir: PathBuf, portable: bool) -> StorageContext {
+        StorageContext { config_dir, portable }
+    }
+
+    pub fn settings_path(&self) -> PathBuf {
+        settings_path(&self.config_dir)
+    }
+
+    pub fn games_path(&self) -> PathBuf {
+        games_path(&self.config_dir)
+    }
+
+    pub fn history_path(&self) -> PathBuf {
+        history_path(&self.config_dir)
+    }
+
+    pub fn traces_dir(&self) -> PathBuf {
+        traces_dir(&self.config_dir)
+    }
+
+    pub fn trace_path(&self, name: &str) -> PathBuf {
+        trace_path(&self.traces_dir(), name)
+    }
+}
+
+// Picks the config folder. A folder given on the command line wins over CONFIG_DIR_ENV, then
+// portable mode keeps everything in a data folder next to the executable, and otherwise the
+// platform's config folder is used. Returns the folder and whether it is portable.
+pub fn resolve_config_dir(
+    flag_dir: Option<PathBuf>,
+    env_dir: Option<PathBuf>,
+    portable: bool,
+    exe_dir: Option<&Path>,
+    platform_dir: Option<PathBuf>,
+) -> Result<(PathBuf, bool), String> {
+    if let Some(dir) = flag_dir.or(env_dir) {
+        return Ok((dir, false));
+    }
+
+    match exe_dir {
+        Some(exe_dir) if portable || exe_dir.join(PORTABLE_MARKER).exists() => {
+            return Ok((exe_dir.join("data"), true));
+        }
+        None if portable => return Err("Portable mode needs the folder of the executable".to_string()),
+        _ => {}
+    }
+
+    platform_dir
+        .map(|dir| (dir.join("AimCalibrate"), false))
+        .ok_or_else(|| format!("No config folder found, pass --config-dir or set {}", CONFIG_DIR_ENV))
+}
+
+pub fn save_app_settings(path: &Path, settings: &AppSettings) -> Result<(), Box<dyn std::error::Error>> {
+    let data = serde_json::to_string(settings)?;
+    save_json_file(path, &data)
+}
+
+// Returns the settings together with the problem found if the file was corrupt. The corrupt file
+// is moved aside and the newest good backup is used, or the defaults if there is none.
+pub fn load_app_settings(path: &Path) -> Result<(AppSettings, Option<StorageIssue>), Box<dyn std::error::Error>> {
+    let (settings, issue) = load_json_file::<AppSettings>(path)?;
+    let mut settings = settings.unwrap_or_default();
+
+    // Ensure that the `hotkeys` vector is populated
+    if settings.hotkeys.is_empty() {
+        settings.hotkeys = vec![
+            "F1".to_string(),
+            "F2".to_string(),
+            "F3".to_string(),
+            "F4".to_string(),
+        ];
+    }
+
+    // Save the updated settings back to the file
+    save_app_settings(path, &settings)?;
+
+    Ok((settings, issue))
+}
+
+// Reads the settings without saving them or moving a corrupt file aside, for the command line.
+// A missing file gives the defaults.
+pub fn read_app_settings(path: &Path) -> Result<AppSettings, Box<dyn std::error::Error>> {
+    if path.exists() {
+        let data = fs::read_to_string(path)?;
+        let settings: AppSettings = serde_json::from_str(&data)?;
+        Ok(settings)
+    } else {
+        Ok(AppSettings::default())
+    }
+}
+
+// Reads a game list without touching the file, for lists shared between users
+pub fn load_yaw_data(path: &Path) -> Result<Vec<GameYaw>, Box<dyn std::error::Error>> {
+    if path.exists() {
+        let data = fs::read_to_string(path)?;
+        let game_yaws: Vec<GameYaw> = serde_json::from_str(&data)?;
+        Ok(game_yaws)
+    } else {
+        Ok(vec![])
+    }
+}
+
+// Reads the app's own Games.json, recovering from a corrupt file the same way as the settings
+pub fn load_games(path: &Path) -> Result<(Vec<GameYaw>, Option<StorageIssue>), Box<dyn std::error::Error>> {
+    let (games, issue) = load_json_file::<Vec<GameYaw>>(path)?;
+    Ok((games.unwrap_or_default(), issue))
+}
+
+pub fn save_yaw_data(path: &Path, game_yaws: &[GameYaw]) -> Result<(), Box<dyn std::error::Error>> {
+    let data = serde_json::to_string(game_yaws)?;
+    save_json_file(path, &data)
+}
+
+// The history is one JSON object per line, so a measurement only ever appends to the file
+pub fn append_history(path: &Path, record: &MeasurementRecord) -> Result<(), Box<dyn std::error::Error>> {
+    let mut line = serde_json::to_string(record)?;
+    line.push('\n');
+
+    create_parent_dir(path)?;
+    let mut file = fs::OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(line.as_bytes())?;
+    Ok(())
+}
+
+// Lines that don't parse, like one cut off by a crash, are skipped instead of failing the whole history
+pub fn load_history(path: &Path) -> Result<Vec<MeasurementRecord>, Box<dyn std::error::Error>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let data = fs::read_to_string(path)?;
+    Ok(data
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+pub fn save_trace(path: &Path, trace: &MovementTrace) -> Result<(), Box<dyn std::error::Error>> {
+    let data = serde_json::to_string(trace)?;
+    write_atomic(path, &data)?;
+    Ok(())
+}
+
+pub fn load_trace(path: &Path) -> Result<MovementTrace, Box<dyn std::error::Error>> {
+    let data = fs::read_to_string(path)?;
+    let trace: MovementTrace = serde_json::from_str(&data)?;
+    Ok(trace)
+}
+
+pub fn list_traces(traces_dir: &Path) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let mut names = Vec::new();
+    if !traces_dir.exists() {
+        return Ok(names);
+    }
+
+    for entry in fs::read_dir(traces_dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "trace") {
+            if let Some(stem) = path.file_stem() {
+                names.push(stem.to_string_lossy().to_string());
+            }
+        }
+    }
+
+    names.sort();
+    Ok(names)
+}
+
+// Copies the file next to itself the first time it is replaced, then writes through a temporary
+// file so a failed write never leaves the game with half a config. Later writes keep that first
+// copy, so the backup is always the config as it was before AimCalibrate touched it. Returns the
+// path of the backup.
+pub fn write_with_backup(path: &Path, content: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let backup = with_suffix(path, ".aimcalibrate.bak");
+    if !backup.exists() {
+        fs::copy(path, &backup)?;
+    }
+    write_atomic(path, content)?;
+
+    Ok(backup)
+}
+
+pub fn backup_path(path: &Path, index: usize) -> PathBuf {
+    with_suffix(path, &format!(".bak{}", index))
+}
+
+// Writes to a temporary file and renames it over the old one, so a crash or power loss leaves
+// either the old or the new content but never a truncated file
+pub fn write_atomic(path: &Path, content: &str) -> std::io::Result<()> {
+    create_parent_dir(path)?;
+    let temp = with_suffix(path, ".tmp");
+    let mut file = fs::File::create(&temp)?;
+    file.write_all(content.as_bytes())?;
+    file.sync_all()?;
+    fs::rename(&temp, path)
+}
+
+fn create_parent_dir(path: &Path) -> std::io::Result<()> {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => fs::create_dir_all(parent),
+        _ => Ok(()),
+    }
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
+fn save_json_file(path: &Path, data: &str) -> Result<(), Box<dyn std::error::Error>> {
+    rotate_backups(path, data)?;
+    write_atomic(path, data)?;
+    Ok(())
+}
+
+// Shifts the backups by one and keeps the current file as the newest. Unchanged and corrupt
+// files are skipped, so saving the same data or a bad file never pushes out a good backup.
+fn rotate_backups(path: &Path, new_data: &str) -> std::io::Result<()> {
+    let current = match fs::read_to_string(path) {
+        Ok(current) => current,
+        Err(_) => return Ok(()),
+    };
+    if current == new_data || serde_json::from_str::<serde_json::Value>(&current).is_err() {
+        return Ok(());
+    }
+
+    for index in (1..BACKUP_COUNT).rev() {
+        let backup = backup_path(path, index);
+        if backup.exists() {
+            fs::rename(&backup, backup_path(path, index + 1))?;
+        }
+    }
+    write_atomic(&backup_path(path, 1), &current)
+}
+
+// A name for the corrupt file that no earlier quarantined copy has, even when two show up in the
+// same millisecond
+fn quarantine_path(path: &Path) -> PathBuf {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|time| time.as_millis())
+        .unwrap_or_default();
+
+    let mut quarantined = with_suffix(path, &format!(".corrupt-{}", millis));
+    let mut index = 1;
+    while quarantined.exists() {
+        quarantined = with_suffix(path, &format!(".corrupt-{}-{}", millis, index));
+        index += 1;
+    }
+    quarantined
+}
+
+// Reads a JSON file written by save_json_file. A file that doesn't parse is renamed instead of
+// being overwritten later, and the newest backup that parses takes its place.
+fn load_json_file<T: DeserializeOwned>(
+    path: &Path,
+) -> Result<(Option<T>, Option<StorageIssue>), Box<dyn std::error::Error>> {
+    let data = match fs::read(path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok((None, None)),
+        Err(e) => return Err(e.into()),
+    };
+    let error = match serde_json::from_slice::<T>(&data) {
+        Ok(value) => return Ok((Some(value), None)),
+        Err(e) => e,
+    };
+
+    let quarantined = quarantine_path(path);
+    fs::rename(path, &quarantined)?;
+
+    let mut restored = None;
+    for index in 1..=BACKUP_COUNT {
+        let backup = backup_path(path, index);
+        let Ok(data) = fs::read_to_string(&backup) else {
+            continue;
+        };
+        if let Ok(value) = serde_json::from_str::<T>(&data) {
+            write_atomic(path, &data)?;
+            restored = Some((value, backup));
+            break;
+        }
+    }
+
+    let issue = StorageIssue {
+        path: path.to_string_lossy().to_string(),
+        error: error.to_string(),
+        quarantined: quarantined.to_string_lossy().to_string(),
+        restored_from: restored.as_ref().map(|(_, backup)| backup.to_string_lossy().to_string()),
+    };
+
+    Ok((restored.map(|(value, _)| value), Some(issue)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{MeasurementKind, ScopeConvention, SensGranularity, UserSettings};
+
+    fn game(name: &str, yaw: f64) -> GameYaw {
+        GameYaw {
+            name: name.to_string(),
+            yaw,
+            yaw_curve: None,
+            raw_input: true,
+            granularity: SensGranularity::default(),
+            scopes: Vec::new(),
+            scope_convention: ScopeConvention::default(),
+            gamepad: None,
+            config: None,
+            steam_app_id: None,
+        }
+    }
+
+    fn profile() -> UserSettings {
+        UserSettings {
+            cm360: 30.0,
+            dpi: 800,
+            normal_fov: 103.0,
+            scoped_fov: 40.0,
+            game_sens: 1.0,
+            game_fov: 103.0,
+            game: None,
+        }
+    }
+
+    #[test]
+    fn settings_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = settings_path(dir.path());
+        let settings = AppSettings {
+            pointer_speed: 6,
+            api_port: 4000,
+            ..AppSettings::default()
+        };
+
+        save_app_settings(&path, &settings).unwrap();
+        let (loaded, issue) = load_app_settings(&path).unwrap();
+
+        assert!(issue.is_none());
+        assert_eq!(loaded.pointer_speed, 6);
+        assert_eq!(loaded.api_port, 4000);
+        assert_eq!(loaded.hotkeys, settings.hotkeys);
+    }
+
+    #[test]
+    fn missing_files_load_as_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let (settings, issue) = load_app_settings(&settings_path(dir.path())).unwrap();
+        assert!(issue.is_none());
+        assert_eq!(settings.hotkeys.len(), 4);
+
+        let (games, issue) = load_games(&games_path(dir.path())).unwrap();
+        assert!(games.is_empty() && issue.is_none());
+        assert!(load_history(&history_path(dir.path())).unwrap().is_empty());
+    }
+
+    #[test]
+    fn reading_never_changes_the_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let settings = settings_path(dir.path());
+        let games = games_path(dir.path());
+        fs::write(&settings, "{\"turn_speed\": 2.0, \"hotkeys\": []}").unwrap();
+        fs::write(&games, "not json").unwrap();
+
+        assert!(read_app_settings(&settings).unwrap().hotkeys.is_empty());
+        assert!(load_yaw_data(&games).is_err());
+
+        assert_eq!(fs::read_to_string(&settings).unwrap(), "{\"turn_speed\": 2.0, \"hotkeys\": []}");
+        assert_eq!(fs::read_to_string(&games).unwrap(), "not json");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn games_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = games_path(dir.path());
+
+        save_yaw_data(&path, &[game("CS2", 0.022), game("Valorant", 0.07)]).unwrap();
+        let (games, issue) = load_games(&path).unwrap();
+
+        assert!(issue.is_none());
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[1].name, "Valorant");
+        assert_eq!(games[1].yaw, 0.07);
+        assert_eq!(load_yaw_data(&path).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn saving_keeps_the_previous_file_as_a_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = games_path(dir.path());
+
+        save_yaw_data(&path, &[game("CS2", 0.022)]).unwrap();
+        save_yaw_data(&path, &[game("CS2", 0.022)]).unwrap();
+        assert!(!backup_path(&path, 1).exists());
+
+        save_yaw_data(&path, &[game("Valorant", 0.07)]).unwrap();
+        let backup = load_yaw_data(&backup_path(&path, 1)).unwrap();
+        assert_eq!(backup[0].name, "CS2");
+    }
+
+    #[test]
+    fn corrupt_file_is_quarantined_and_the_backup_restored() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = games_path(dir.path());
+        save_yaw_data(&path, &[game("CS2", 0.022)]).unwrap();
+        save_yaw_data(&path, &[game("Valorant", 0.07)]).unwrap();
+        fs::write(&path, "[{\"name\": ").unwrap();
+
+        let (games, issue) = load_games(&path).unwrap();
+        let issue = issue.unwrap();
+
+        assert_eq!(games[0].name, "CS2");
+        assert_eq!(issue.restored_from, Some(backup_path(&path, 1).to_string_lossy().to_string()));
+        assert_eq!(fs::read_to_string(&issue.quarantined).unwrap(), "[{\"name\": ");
+        assert_eq!(load_yaw_data(&path).unwrap()[0].name, "CS2");
+    }
+
+    #[test]
+    fn every_corrupt_file_is_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = games_path(dir.path());
+
+        let mut quarantined = Vec::new();
+        for content in ["[{", "[{\"name\"", "{]"] {
+            fs::write(&path, content).unwrap();
+            let (_, issue) = load_games(&path).unwrap();
+            quarantined.push(issue.unwrap().quarantined);
+        }
+
+        assert_eq!(fs::read_to_string(&quarantined[0]).unwrap(), "[{");
+        assert_eq!(fs::read_to_string(&quarantined[2]).unwrap(), "{]");
+        quarantined.sort();
+        quarantined.dedup();
+        assert_eq!(quarantined.len(), 3);
+    }
+
+    #[test]
+    fn corrupt_settings_without_backup_fall_back_to_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = settings_path(dir.path());
+        fs::write(&path, "not json").unwrap();
+
+        let (settings, issue) = load_app_settings(&path).unwrap();
+
+        assert_eq!(settings.api_port, AppSettings::default().api_port);
+        assert!(issue.unwrap().restored_from.is_none());
+    }
+
+    #[test]
+    fn history_skips_broken_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = history_path(dir.path());
+        let mut record = crate::history::new_record(MeasurementKind::Cm360, "turn", 9449, 30.0, None, &profile(), 1.0);
+
+        append_history(&path, &record).unwrap();
+        // A line cut off by a crash
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"{\"timestamp\": 1\n")
+            .unwrap();
+        record.kind = MeasurementKind::Fov;
+        append_history(&path, &record).unwrap();
+
+        let history = load_history(&path).unwrap();
+        assert_eq!(history.len(), 2);
+        assert!(history[1].kind == MeasurementKind::Fov);
+        assert_eq!(history[0].counts, 9449);
+    }
+
+    #[test]
+    fn traces_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let traces = traces_dir(dir.path());
+        fs::create_dir_all(&traces).unwrap();
+        let trace = MovementTrace {
+            version: 1,
+            recorded_at: 1700000000,
+            device: Some("G Pro".to_string()),
+            page: "measure_cm".to_string(),
+            settings: profile(),
+            dpi_correction: 1.0,
+            yaw_sens: 1.0,
+            packets: vec![(0, 10, 0), (1000, 12, -1)],
+        };
+
+        save_trace(&trace_path(&traces, "slow turn/1"), &trace).unwrap();
+        let names = list_traces(&traces).unwrap();
+        assert_eq!(names, vec!["slow_turn_1".to_string()]);
+
+        let loaded = load_trace(&trace_path(&traces, &names[0])).unwrap();
+        assert_eq!(loaded.packets, trace.packets);
+        assert_eq!(loaded.device, trace.device);
+    }
+
+    #[test]
+    fn config_dir_precedence() {
+        let exe = tempfile::tempdir().unwrap();
+        let flag = Some(PathBuf::from("/flag"));
+        let env = Some(PathBuf::from("/env"));
+        let platform = Some(PathBuf::from("/config"));
+
+        assert_eq!(
+            resolve_config_dir(flag, env.clone(), true, Some(exe.path()), platform.clone()),
+            Ok((PathBuf::from("/flag"), false))
+        );
+        assert_eq!(
+            resolve_config_dir(None, env, false, Some(exe.path()), platform.clone()),
+            Ok((PathBuf::from("/env"), false))
+        );
+        assert_eq!(
+            resolve_config_dir(None, None, false, Some(exe.path()), platform.clone()),
+            Ok((PathBuf::from("/config/AimCalibrate"), false))
+        );
+
+        fs::write(exe.path().join(PORTABLE_MARKER), "").unwrap();
+        assert_eq!(
+            resolve_config_dir(None, None, false, Some(exe.path()), platform),
+            Ok((exe.path().join("data"), true))
+        );
+        assert!(resolve_config_dir(None, None, true, None, None).is_err());
+        assert!(resolve_config_dir(None, None, false, None, None).is_err());
+    }
+
+    #[test]
+    fn write_with_backup_keeps_the_original() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.cfg");
+        fs::write(&path, "sensitivity \"1\"\n").unwrap();
+
+        let backup = write_with_backup(&path, "sensitivity \"2\"\n").unwrap();
+        write_with_backup(&path, "sensitivity \"3\"\n").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "sensitivity \"3\"\n");
+        assert_eq!(fs::read_to_string(&backup).unwrap(), "sensitivity \"1\"\n");
+    }
+
+    #[test]
+    fn folders_are_created_by_the_first_write() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = StorageContext::new(dir.path().join("config"), false);
+
+        assert!(read_app_settings(&storage.settings_path()).is_ok());
+        assert!(load_yaw_data(&storage.games_path()).unwrap().is_empty());
+        assert!(list_traces(&storage.traces_dir()).unwrap().is_empty());
+        assert!(!storage.config_dir.exists());
+
+        save_yaw_data(&storage.games_path(), &[game("Test", 0.022)]).unwrap();
+        assert_eq!(load_yaw_data(&storage.games_path()).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,64 @@
+use crate::calculations::{calculate_measured_cm, calculate_measured_fov, calculate_yaw};
+use crate::input_analysis::analyze_polling;
+use crate::models::{MousePacket, MovementTrace, TraceReplay, UserSettings};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const TRACE_VERSION: u32 = 1;
+
+pub fn create_trace(
+    packets: &[MousePacket],
+    device: Option<String>,
+    page: String,
+    settings: UserSettings,
+    dpi_correction: f64,
+    yaw_sens: f64,
+) -> MovementTrace {
+    let recorded_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    MovementTrace {
+        version: TRACE_VERSION,
+        recorded_at,
+        device,
+        page,
+        settings,
+        dpi_correction,
+        yaw_sens,
+        packets: packets.iter().map(|p| (p.time_us, p.dx, p.dy)).collect(),
+    }
+}
+
+pub fn trace_packets(trace: &MovementTrace) -> Vec<MousePacket> {
+    trace
+        .packets
+        .iter()
+        .map(|&(time_us, dx, dy)| MousePacket { time_us, dx, dy })
+        .collect()
+}
+
+// Reruns every measurement the trace could have been recorded for, the same way the hotkeys compute them
+pub fn replay_trace(trace: &MovementTrace) -> TraceReplay {
+    let packets = trace_packets(trace);
+    let counts: i32 = packets.iter().map(|p| p.dx).sum();
+    let settings = &trace.settings;
+
+    let cm_per360 = calculate_measured_cm(counts, settings.dpi, trace.dpi_correction);
+    let fov16 = calculate_measured_fov(
+        settings.cm360,
+        settings.dpi,
+        trace.dpi_correction,
+        settings.game_sens,
+        counts,
+    );
+    let yaw = calculate_yaw(counts.abs(), trace.yaw_sens);
+
+    TraceReplay {
+        counts,
+        cm_per360,
+        fov16,
+        yaw,
+        polling: analyze_polling(&packets),
+    }
+}
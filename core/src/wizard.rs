@@ -0,0 +1,200 @@
+use crate::calculations::{estimate_fov, pointer_speed_multiplier};
+use crate::models::{CalibrationPlan, CalibrationWizard, GameYaw, Scope, SensGranularity, WizardStep, YawStuff};
+use crate::scopes::calculate_scope_table;
+
+pub fn new_wizard(plan: CalibrationPlan) -> CalibrationWizard {
+    CalibrationWizard {
+        step: WizardStep::MeasureYaw,
+        game: plan.game,
+        sens: plan.sens,
+        cm_per360: plan.cm_per360,
+        dpi: plan.dpi,
+        raw_input: plan.raw_input,
+        scope_convention: plan.scope_convention,
+        monitor_distance: plan.monitor_distance,
+        scopes: plan
+            .scopes
+            .into_iter()
+            .map(|name| Scope {
+                name,
+                fov: None,
+                zoom: None,
+            })
+            .collect(),
+        scope_index: 0,
+        yaw: None,
+        hipfire_fov: None,
+        pending: None,
+        table: None,
+    }
+}
+
+// Page whose hotkey actions measure the current step
+pub fn wizard_page(wizard: &CalibrationWizard) -> Option<&'static str> {
+    match wizard.step {
+        WizardStep::MeasureYaw => Some("measure_yaw"),
+        WizardStep::MeasureHipfireFov | WizardStep::MeasureScopeFov => Some("measure_fov"),
+        WizardStep::Idle | WizardStep::Done => None,
+    }
+}
+
+// Takes the yaw found by the yaw measurement, including later corrections of the estimate
+pub fn set_wizard_yaw(wizard: &mut CalibrationWizard, yaw: &YawStuff) {
+    if wizard.step == WizardStep::MeasureYaw && yaw.counts != 0 {
+        wizard.sens = yaw.sens;
+        wizard.pending = Some(yaw.yaw);
+    }
+}
+
+// FOV from moving the crosshair from the center to a point at the edge of the view. The turn is
+// made at the sensitivity the yaw was measured at, so the in-game settings never have to change.
+pub fn measure_wizard_fov(wizard: &mut CalibrationWizard, counts: i32) -> Option<f64> {
+    if !matches!(wizard.step, WizardStep::MeasureHipfireFov | WizardStep::MeasureScopeFov) {
+        return None;
+    }
+
+    let fov = estimate_fov(wizard.sens, wizard.yaw?, counts.abs());
+    wizard.pending = Some(fov);
+    Some(fov)
+}
+
+// Confirms the result of the current step and moves on to the next one
+pub fn advance_wizard(wizard: &mut CalibrationWizard) -> Result<(), String> {
+    if matches!(wizard.step, WizardStep::Idle | WizardStep::Done) {
+        return Err("The calibration wizard isn't running".to_string());
+    }
+    let value = wizard.pending.take().ok_or("Measure this step before moving on")?;
+
+    match wizard.step {
+        WizardStep::MeasureYaw => {
+            wizard.yaw = Some(value);
+            wizard.step = WizardStep::MeasureHipfireFov;
+        }
+        WizardStep::MeasureHipfireFov => {
+            wizard.hipfire_fov = Some(value);
+            wizard.scope_index = 0;
+            wizard.step = if wizard.scopes.is_empty() {
+                WizardStep::Done
+            } else {
+                WizardStep::MeasureScopeFov
+            };
+        }
+        _ => {
+            wizard.scopes[wizard.scope_index].fov = Some(value);
+            wizard.scope_index += 1;
+            if wizard.scope_index >= wizard.scopes.len() {
+                wizard.step = WizardStep::Done;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// The game entry for everything measured so far. Like a saved yaw, the pointer speed is taken out
+// for games without raw input.
+pub fn wizard_game(wizard: &CalibrationWizard, pointer_speed: u32) -> GameYaw {
+    let mut yaw = wizard.yaw.unwrap_or_default();
+    if !wizard.raw_input {
+        yaw /= pointer_speed_multiplier(pointer_speed);
+    }
+
+    GameYaw {
+        name: wizard.game.clone(),
+        yaw,
+        yaw_curve: None,
+        raw_input: wizard.raw_input,
+        granularity: SensGranularity::default(),
+        scopes: wizard.scopes.iter().filter(|scope| scope.fov.is_some()).cloned().collect(),
+        scope_convention: wizard.scope_convention,
+        gamepad: None,
+        config: None,
+        steam_app_id: None,
+    }
+}
+
+// Hipfire sensitivity for the plan's cm/360 and DPI and the matching scope sensitivities
+pub fn finish_wizard(wizard: &mut CalibrationWizard, dpi_correction: f64, pointer_speed: u32) {
+    let Some(hipfire_fov) = wizard.hipfire_fov else {
+        return;
+    };
+
+    wizard.table = calculate_scope_table(
+        &wizard_game(wizard, pointer_speed),
+        wizard.cm_per360,
+        wizard.dpi,
+        hipfire_fov,
+        wizard.monitor_distance,
+        dpi_correction,
+        pointer_speed,
+    )
+    .ok();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ScopeConvention;
+
+    fn plan() -> CalibrationPlan {
+        CalibrationPlan {
+            game: "Test".to_string(),
+            sens: 2.0,
+            cm_per360: 30.0,
+            dpi: 800,
+            raw_input: true,
+            scope_convention: ScopeConvention::Multiplier,
+            scopes: vec!["4x".to_string()],
+            monitor_distance: 0.0,
+        }
+    }
+
+    fn turn(yaw: f64) -> YawStuff {
+        YawStuff {
+            sens: 2.0,
+            counts: 8182,
+            inc: 0.0,
+            yaw,
+            lower_limit: 0.0,
+            upper_limit: 0.0,
+        }
+    }
+
+    #[test]
+    fn steps_run_in_order_and_need_a_measurement() {
+        let mut wizard = new_wizard(plan());
+        assert_eq!(wizard_page(&wizard), Some("measure_yaw"));
+        assert!(advance_wizard(&mut wizard).is_err());
+
+        set_wizard_yaw(&mut wizard, &turn(0.022));
+        advance_wizard(&mut wizard).unwrap();
+        assert!(wizard.step == WizardStep::MeasureHipfireFov);
+        assert_eq!(wizard_page(&wizard), Some("measure_fov"));
+
+        // Half the FOV is turned to reach the edge of the view
+        assert_eq!(measure_wizard_fov(&mut wizard, 2000), Some(0.022 * 2000.0 * 2.0 * 2.0));
+        advance_wizard(&mut wizard).unwrap();
+        assert!(wizard.step == WizardStep::MeasureScopeFov);
+
+        measure_wizard_fov(&mut wizard, -500).unwrap();
+        advance_wizard(&mut wizard).unwrap();
+        assert!(wizard.step == WizardStep::Done);
+        assert!(advance_wizard(&mut wizard).is_err());
+    }
+
+    #[test]
+    fn finish_uses_the_plan_cm_per360() {
+        let mut wizard = new_wizard(plan());
+        wizard.yaw = Some(0.022);
+        wizard.hipfire_fov = Some(103.0);
+        wizard.scopes[0].fov = Some(40.0);
+
+        finish_wizard(&mut wizard, 1.0, 10);
+        let table = wizard.table.unwrap();
+
+        let cm_per360 = 360.0 / (table.hipfire_sens * 0.022) / 800.0 * 2.54;
+        assert!((cm_per360 - 30.0).abs() < 1e-9);
+        assert_eq!(table.rows.len(), 1);
+        assert!(table.rows[0].sens.is_finite() && table.rows[0].sens > 0.0);
+    }
+}
@@ -0,0 +1,404 @@
+use aimcalibrate_core::calculations::convert_unit;
+use aimcalibrate_core::models::{AppSettings, GameList, SensUnit, UserSettings};
+use crate::utils::handle_hotkey;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::io::{BufRead, BufReader, ErrorKind, Read, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{channel, Sender, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+use tungstenite::handshake::derive_accept_key;
+use tungstenite::protocol::Role;
+use tungstenite::{Message, WebSocket};
+
+// How often the listener checks whether the API was turned off or moved to another port
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+const MAX_BODY_SIZE: usize = 64 * 1024;
+// Limits for the request line and headers, so a client can't make a connection buffer without end
+const MAX_LINE_SIZE: usize = 8 * 1024;
+const MAX_HEADERS: usize = 64;
+// Each connection has its own thread, WebSocket clients included. Connections beyond this get a 503.
+const MAX_CONNECTIONS: usize = 32;
+
+static SERVER_RUNNING: AtomicBool = AtomicBool::new(false);
+static OPEN_CONNECTIONS: AtomicUsize = AtomicUsize::new(0);
+
+// One sender per connected WebSocket client
+static CLIENTS: Lazy<Mutex<Vec<Sender<String>>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+#[derive(Deserialize)]
+struct ConvertRequest {
+    value: f64,
+    from: SensUnit,
+    dpi: i32,
+    to: SensUnit,
+    new_dpi: i32,
+}
+
+#[derive(Deserialize)]
+struct HotkeyRequest {
+    hotkey: usize,
+    // Page whose hotkey action runs, defaults to the page open in the app. The app stays on its page.
+    page: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ApiEvent<'a, S: Serialize> {
+    event: &'a str,
+    payload: &'a S,
+}
+
+struct Request {
+    method: String,
+    path: String,
+    token: Option<String>,
+    websocket_key: Option<String>,
+    body: Vec<u8>,
+}
+
+// 128 bits from the OS random number generator, hex encoded
+pub fn generate_api_token() -> Result<String, String> {
+    let mut bytes = [0u8; 16];
+    getrandom::getrandom(&mut bytes).map_err(|e| format!("Failed to generate an API token: {}", e))?;
+
+    Ok(bytes.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+// Looks at every byte instead of stopping at the first difference, so the time a request takes
+// doesn't tell how much of a guessed token was right
+fn token_matches(token: &str, given: Option<&str>) -> bool {
+    let Some(given) = given else {
+        return false;
+    };
+
+    !token.is_empty()
+        && token.len() == given.len()
+        && token.bytes().zip(given.bytes()).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+// Whether the API is still on and `token` is still the one in the settings
+fn token_current(app_handle: &AppHandle, token: &str) -> bool {
+    api_settings(app_handle).is_some_and(|(enabled, _, current)| enabled && token_matches(&current, Some(token)))
+}
+
+// Starts the API if it is turned on and not already running. The listener stops by itself
+// once the API is turned off or its port changes, so calling this again applies new settings.
+pub fn start_api_server(app_handle: AppHandle) {
+    let port = {
+        let settings_state: State<Arc<Mutex<AppSettings>>> = app_handle.state();
+        let settings = settings_state.lock().unwrap();
+        if !settings.api_enabled || settings.api_token.is_empty() {
+            return;
+        }
+        settings.api_port
+    };
+
+    if SERVER_RUNNING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+        .and_then(|listener| listener.set_nonblocking(true).map(|_| listener));
+    let listener = match listener {
+        Ok(listener) => listener,
+        Err(e) => {
+            println!("Failed to start control API on port {}: {}", port, e);
+            SERVER_RUNNING.store(false, Ordering::SeqCst);
+            return;
+        }
+    };
+
+    std::thread::spawn(move || {
+        while api_settings(&app_handle).is_some_and(|(enabled, current_port, _)| enabled && current_port == port) {
+            match listener.accept() {
+                Ok((stream, _)) => {
+                    let Some(slot) = ConnectionSlot::take() else {
+                        let _ = write_response(&stream, 503, &json!({ "error": "Too many connections" }));
+                        continue;
+                    };
+                    let app_handle = app_handle.clone();
+                    std::thread::spawn(move || {
+                        let _slot = slot;
+                        if let Err(e) = handle_connection(stream, &app_handle) {
+                            println!("Control API connection failed: {}", e);
+                        }
+                    });
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => std::thread::sleep(POLL_INTERVAL),
+                Err(e) => println!("Control API failed to accept a connection: {}", e),
+            }
+        }
+
+        // Dropping the senders closes all WebSocket connections
+        CLIENTS.lock().unwrap().clear();
+        SERVER_RUNNING.store(false, Ordering::SeqCst);
+        start_api_server(app_handle);
+    });
+}
+
+// Counts an open connection until it is dropped
+struct ConnectionSlot;
+
+impl ConnectionSlot {
+    fn take() -> Option<ConnectionSlot> {
+        OPEN_CONNECTIONS
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |open| (open < MAX_CONNECTIONS).then_some(open + 1))
+            .ok()
+            .map(|_| ConnectionSlot)
+    }
+}
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        OPEN_CONNECTIONS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+// Sends an event to every connected WebSocket client
+pub fn broadcast<S: Serialize>(event: &str, payload: &S) {
+    let mut clients = CLIENTS.lock().unwrap();
+    if clients.is_empty() {
+        return;
+    }
+
+    let message = serde_json::to_string(&ApiEvent { event, payload }).unwrap();
+    clients.retain(|client| client.send(message.clone()).is_ok());
+}
+
+fn api_settings(app_handle: &AppHandle) -> Option<(bool, u16, String)> {
+    let settings_state: State<Arc<Mutex<AppSettings>>> = app_handle.state();
+    let settings = settings_state.lock().ok()?;
+    Some((settings.api_enabled, settings.api_port, settings.api_token.clone()))
+}
+
+fn handle_connection(stream: TcpStream, app_handle: &AppHandle) -> std::io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+
+    let mut reader = BufReader::new(&stream);
+    let request = match read_request(&mut reader)? {
+        Some(request) => request,
+        None => return write_response(&stream, 400, &json!({ "error": "Invalid request" })),
+    };
+    drop(reader);
+
+    if request.method == "OPTIONS" {
+        return write_response(&stream, 204, &serde_json::Value::Null);
+    }
+
+    let token = api_settings(app_handle).map(|(_, _, token)| token).unwrap_or_default();
+    if !token_matches(&token, request.token.as_deref()) {
+        return write_response(&stream, 401, &json!({ "error": "Missing or wrong token" }));
+    }
+
+    if let Some(key) = &request.websocket_key {
+        if request.path == "/events" {
+            return serve_events(stream, key, &token, app_handle);
+        }
+    }
+
+    let (status, body) = route(&request, app_handle);
+    write_response(&stream, status, &body)
+}
+
+fn route(request: &Request, app_handle: &AppHandle) -> (u16, serde_json::Value) {
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/games") => {
+            let state: State<Arc<Mutex<GameList>>> = app_handle.state();
+            let games = state.lock().unwrap().games.clone();
+            (200, json!(games))
+        }
+        ("GET", "/settings") => {
+            let state: State<Arc<Mutex<UserSettings>>> = app_handle.state();
+            let settings = state.lock().unwrap().clone();
+            (200, json!(settings))
+        }
+        ("POST", "/convert") => match serde_json::from_slice::<ConvertRequest>(&request.body) {
+            Ok(convert) => {
+                let (dpi_correction, pointer_speed) = {
+                    let state: State<Arc<Mutex<AppSettings>>> = app_handle.state();
+                    let settings = state.lock().unwrap();
+                    (settings.dpi_correction, settings.pointer_speed)
+                };
+                match convert_unit(
+                    convert.value,
+                    &convert.from,
+                    convert.dpi,
+                    &convert.to,
+                    convert.new_dpi,
+                    dpi_correction,
+                    pointer_speed,
+                ) {
+                    Ok(conversion) => (200, json!(conversion)),
+                    Err(e) => (400, json!({ "error": e })),
+                }
+            }
+            Err(e) => (400, json!({ "error": e.to_string() })),
+        },
+        ("POST", "/hotkey") => match serde_json::from_slice::<HotkeyRequest>(&request.body) {
+            Ok(hotkey) if (1..=4).contains(&hotkey.hotkey) => {
+                // Turns take a while, the results arrive over /events
+                let app_handle = app_handle.clone();
+                std::thread::spawn(move || handle_hotkey(hotkey.hotkey - 1, hotkey.page.as_deref(), &app_handle));
+                (202, json!({ "hotkey": hotkey.hotkey }))
+            }
+            Ok(_) => (400, json!({ "error": "hotkey must be between 1 and 4" })),
+            Err(e) => (400, json!({ "error": e.to_string() })),
+        },
+        _ => (404, json!({ "error": "Not found" })),
+    }
+}
+
+// Reads one line of the request head. Returns false for a line that is too long or cut off.
+fn read_head_line(reader: &mut BufReader<&TcpStream>, line: &mut String) -> std::io::Result<bool> {
+    line.clear();
+    reader.by_ref().take(MAX_LINE_SIZE as u64).read_line(line)?;
+    Ok(line.ends_with('\n'))
+}
+
+fn read_request(reader: &mut BufReader<&TcpStream>) -> std::io::Result<Option<Request>> {
+    let mut line = String::new();
+    if !read_head_line(reader, &mut line)? {
+        return Ok(None);
+    }
+    let mut parts = line.split_whitespace();
+    let (method, target) = match (parts.next(), parts.next()) {
+        (Some(method), Some(target)) => (method.to_string(), target.to_string()),
+        _ => return Ok(None),
+    };
+
+    let (path, query) = target.split_once('?').unwrap_or((target.as_str(), ""));
+    let mut request = Request {
+        method,
+        path: path.to_string(),
+        // Browsers can't set headers on a WebSocket, so the token may also be passed in the URL
+        token: query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("token="))
+            .map(str::to_string),
+        websocket_key: None,
+        body: Vec::new(),
+    };
+
+    let mut content_length = 0;
+    for headers in 0.. {
+        if !read_head_line(reader, &mut line)? {
+            return Ok(None);
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if headers == MAX_HEADERS {
+            return Ok(None);
+        }
+
+        let Some((name, value)) = header.split_once(':') else {
+            return Ok(None);
+        };
+        let value = value.trim();
+        match name.trim().to_ascii_lowercase().as_str() {
+            "content-length" => content_length = value.parse().unwrap_or(0),
+            "authorization" => {
+                if let Some(token) = value.strip_prefix("Bearer ") {
+                    request.token = Some(token.trim().to_string());
+                }
+            }
+            "sec-websocket-key" => request.websocket_key = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    if content_length > MAX_BODY_SIZE {
+        return Ok(None);
+    }
+    request.body = vec![0; content_length];
+    reader.read_exact(&mut request.body)?;
+
+    Ok(Some(request))
+}
+
+fn write_response(mut stream: &TcpStream, status: u16, body: &serde_json::Value) -> std::io::Result<()> {
+    let reason = match status {
+        200 => "OK",
+        202 => "Accepted",
+        204 => "No Content",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    };
+    let body = if body.is_null() { String::new() } else { body.to_string() };
+
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\n\
+         Content-Type: application/json\r\n\
+         Content-Length: {}\r\n\
+         Access-Control-Allow-Origin: *\r\n\
+         Access-Control-Allow-Headers: Authorization, Content-Type\r\n\
+         Access-Control-Allow-Methods: GET, POST, OPTIONS\r\n\
+         Connection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+// Streams events until the client leaves. The connection is closed once the API is turned off or
+// a new token is generated, so a client can't keep listening with an old one.
+fn serve_events(mut stream: TcpStream, key: &str, token: &str, app_handle: &AppHandle) -> std::io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 101 Switching Protocols\r\n\
+         Upgrade: websocket\r\n\
+         Connection: Upgrade\r\n\
+         Sec-WebSocket-Accept: {}\r\n\r\n",
+        derive_accept_key(key.as_bytes())
+    )?;
+    stream.flush()?;
+    // Short reads so queued events go out while waiting for messages from the client
+    stream.set_read_timeout(Some(POLL_INTERVAL))?;
+
+    let (sender, receiver) = channel();
+    CLIENTS.lock().unwrap().push(sender);
+    let mut socket = WebSocket::from_raw_socket(stream, Role::Server, None);
+
+    loop {
+        if !token_current(app_handle, token) {
+            let _ = socket.close(None);
+            let _ = socket.flush();
+            return Ok(());
+        }
+
+        loop {
+            match receiver.try_recv() {
+                Ok(message) => {
+                    if socket.send(Message::text(message)).is_err() {
+                        return Ok(());
+                    }
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    let _ = socket.close(None);
+                    let _ = socket.flush();
+                    return Ok(());
+                }
+            }
+        }
+
+        match socket.read() {
+            Ok(Message::Close(_)) => return Ok(()),
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(e)) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(_) => return Ok(()),
+        }
+    }
+}
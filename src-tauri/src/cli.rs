@@ -0,0 +1,567 @@
+use aimcalibrate_core::calculations::{calculate_scoped_counts, convert_unit, fov_to_horizontal, horizontal_to_fov};
+use aimcalibrate_core::history::{filter_history, format_history};
+use aimcalibrate_core::models::{FovScale, GameYaw, HistoryFilter, SensGranularity, SensUnit, TableFormat};
+use aimcalibrate_core::storage::{load_history, load_yaw_data, read_app_settings, save_yaw_data, StorageContext};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const CLI_COMMANDS: [&str; 10] = [
+    "cm360", "sens", "convert", "scoped-counts", "fov", "games", "history", "help", "--help", "-h",
+];
+
+// Options that don't take a value
+const CLI_FLAGS: [&str; 4] = ["json", "no-raw-input", "replace", "help"];
+
+const USAGE: &str = "Usage: aimcalibrate <command> [options] [--json]
+
+Commands:
+  cm360 <sens> --dpi <dpi> (--game <name> | --yaw <yaw>)
+  sens <cm360> --dpi <dpi> (--game <name> | --yaw <yaw>)
+  convert <value> --from <unit> --to <unit> --dpi <dpi> [--new-dpi <dpi>]
+  scoped-counts <cm360> --dpi <dpi> --fov <fov> --scoped-fov <fov>
+  fov <fov> --from <scale> --to <scale> [--aspect 16:9]
+  games list
+  games add <name> --yaw <yaw> [--no-raw-input]
+  games remove <name>
+  games export <file>
+  games import <file> [--replace]
+  games check [file]
+  history [--kind cm360|fov|yaw] [--game <name>] [--device <name>] [--since <unix time>]
+          [--until <unix time>] [--limit <n>] [--format csv|markdown]
+
+Options for all commands and the app:
+  -h, --help             print this help
+  --config-dir <folder>  keep settings and Games.json in this folder
+  --portable             keep them in a data folder next to the executable
+
+Units are game names from Games.json or one of cm360, inches_per360, deg_per_count,
+counts_per360, deg_per_mm. FOV scales are horizontal, 4ml3 and vertical.";
+
+struct CliArgs {
+    positional: Vec<String>,
+    options: HashMap<String, String>,
+    flags: Vec<String>,
+}
+
+impl CliArgs {
+    fn parse(args: &[String]) -> Result<CliArgs, String> {
+        let mut parsed = CliArgs {
+            positional: Vec::new(),
+            options: HashMap::new(),
+            flags: Vec::new(),
+        };
+
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            match arg.strip_prefix("--") {
+                Some(name) if CLI_FLAGS.contains(&name) => parsed.flags.push(name.to_string()),
+                Some(name) => {
+                    let value = iter.next().ok_or_else(|| format!("Missing value for --{}", name))?;
+                    parsed.options.insert(name.to_string(), value.clone());
+                }
+                None => parsed.positional.push(arg.clone()),
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    fn flag(&self, name: &str) -> bool {
+        self.flags.iter().any(|flag| flag == name)
+    }
+
+    fn option(&self, name: &str) -> Result<&str, String> {
+        self.options
+            .get(name)
+            .map(String::as_str)
+            .ok_or_else(|| format!("Missing --{}", name))
+    }
+
+    fn number<T: std::str::FromStr>(&self, name: &str) -> Result<T, String> {
+        self.option(name)?
+            .parse()
+            .map_err(|_| format!("--{} must be a number", name))
+    }
+
+    fn positional_number(&self, index: usize, name: &str) -> Result<f64, String> {
+        self.positional
+            .get(index)
+            .ok_or_else(|| format!("Missing {}", name))?
+            .parse()
+            .map_err(|_| format!("{} must be a number", name))
+    }
+}
+
+// Output of a command, printed as text or as JSON with --json
+struct CliOutput {
+    text: String,
+    json: Value,
+}
+
+// Runs the command line interface if the arguments name a command. Returns the exit code, or None
+// if the app should start normally.
+pub fn run_cli(args: &[String], storage: &StorageContext) -> Option<i32> {
+    let command = args.get(1)?;
+    if !CLI_COMMANDS.contains(&command.as_str()) {
+        return None;
+    }
+
+    attach_console();
+    let cli_args = match CliArgs::parse(&args[2..]) {
+        Ok(cli_args) => cli_args,
+        Err(e) => {
+            eprintln!("{}", e);
+            return Some(2);
+        }
+    };
+    let as_json = cli_args.flag("json");
+    let command = if cli_args.flag("help") || cli_args.positional.iter().any(|arg| arg == "-h") {
+        "help"
+    } else {
+        command.as_str()
+    };
+
+    let result = match command {
+        "cm360" => cm360_command(&cli_args, storage),
+        "sens" => sens_command(&cli_args, storage),
+        "convert" => convert_command(&cli_args, storage),
+        "scoped-counts" => scoped_counts_command(&cli_args, storage),
+        "fov" => fov_command(&cli_args),
+        "games" => games_command(&cli_args, storage),
+        "history" => history_command(&cli_args, storage),
+        _ => Ok(CliOutput {
+            text: USAGE.to_string(),
+            json: json!({ "usage": USAGE }),
+        }),
+    };
+
+    match result {
+        Ok(output) => {
+            if as_json {
+                println!("{}", output.json);
+            } else {
+                println!("{}", output.text);
+            }
+            Some(0)
+        }
+        Err(e) => {
+            if as_json {
+                println!("{}", json!({ "error": e }));
+            } else {
+                eprintln!("{}", e);
+            }
+            Some(1)
+        }
+    }
+}
+
+// Release builds use the windows subsystem and have no console, so print to the one the command was run from
+#[cfg(target_os = "windows")]
+fn attach_console() {
+    unsafe {
+        winapi::um::wincon::AttachConsole(winapi::um::wincon::ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn attach_console() {}
+
+// The command line only reads the files as they are. Recovering a corrupt file is left to the app,
+// so a script never rewrites settings or moves a game list aside.
+fn load_games(storage: &StorageContext) -> Result<Vec<GameYaw>, String> {
+    let path = storage.games_path();
+    load_yaw_data(&path).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+// Same DPI correction and pointer speed as the app uses
+fn app_corrections(storage: &StorageContext) -> Result<(f64, u32), String> {
+    let path = storage.settings_path();
+    read_app_settings(&path)
+        .map(|settings| (settings.dpi_correction, settings.pointer_speed))
+        .map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn parse_unit(name: &str, games: &[GameYaw]) -> Result<SensUnit, String> {
+    if let Ok(unit) = serde_json::from_value::<SensUnit>(json!({ "type": name })) {
+        if !matches!(unit, SensUnit::Game { .. }) {
+            return Ok(unit);
+        }
+    }
+
+    games
+        .iter()
+        .find(|game| game.name.eq_ignore_ascii_case(name))
+        .map(GameYaw::unit)
+        .ok_or_else(|| format!("Unknown unit or game: {}", name))
+}
+
+// --game looks the game up in Games.json, --yaw describes one that isn't saved
+fn game_unit(args: &CliArgs, storage: &StorageContext) -> Result<SensUnit, String> {
+    match args.options.get("game") {
+        Some(name) => parse_unit(name, &load_games(storage)?),
+        None => Ok(SensUnit::Game {
+            yaw: args.number("yaw")?,
+            yaw_curve: None,
+            raw_input: !args.flag("no-raw-input"),
+            granularity: SensGranularity::default(),
+        }),
+    }
+}
+
+fn cm360_command(args: &CliArgs, storage: &StorageContext) -> Result<CliOutput, String> {
+    let sens = args.positional_number(0, "sensitivity")?;
+    let dpi: i32 = args.number("dpi")?;
+    let unit = game_unit(args, storage)?;
+    let (dpi_correction, pointer_speed) = app_corrections(storage)?;
+
+    let conversion = convert_unit(sens, &unit, dpi, &SensUnit::Cm360, dpi, dpi_correction, pointer_speed)?;
+    Ok(CliOutput {
+        text: format!("{:.2} cm/360", conversion.sens),
+        json: json!({ "cm_per360": conversion.sens }),
+    })
+}
+
+fn sens_command(args: &CliArgs, storage: &StorageContext) -> Result<CliOutput, String> {
+    let cm_per360 = args.positional_number(0, "cm/360")?;
+    let dpi: i32 = args.number("dpi")?;
+    let unit = game_unit(args, storage)?;
+    let (dpi_correction, pointer_speed) = app_corrections(storage)?;
+
+    let conversion = convert_unit(cm_per360, &SensUnit::Cm360, dpi, &unit, dpi, dpi_correction, pointer_speed)?;
+    let mut text = format!(
+        "{} (exact {:.6}, {:.2} cm/360, {:+.2}%)",
+        conversion.settable_sens, conversion.sens, conversion.cm_per360, conversion.error_percent
+    );
+    if let Some(dpi) = conversion.suggested_dpi {
+        text.push_str(&format!("\nCloser match at {} DPI", dpi));
+    }
+
+    Ok(CliOutput {
+        text,
+        json: serde_json::to_value(&conversion).map_err(|e| e.to_string())?,
+    })
+}
+
+fn convert_command(args: &CliArgs, storage: &StorageContext) -> Result<CliOutput, String> {
+    let value = args.positional_number(0, "value")?;
+    let dpi: i32 = args.number("dpi")?;
+    let new_dpi: i32 = if args.options.contains_key("new-dpi") { args.number("new-dpi")? } else { dpi };
+    let games = load_games(storage)?;
+    let from = parse_unit(args.option("from")?, &games)?;
+    let to = parse_unit(args.option("to")?, &games)?;
+    let (dpi_correction, pointer_speed) = app_corrections(storage)?;
+
+    let conversion = convert_unit(value, &from, dpi, &to, new_dpi, dpi_correction, pointer_speed)?;
+    Ok(CliOutput {
+        text: format!(
+            "{} (exact {:.6}, {:.2} cm/360, {:+.2}%)",
+            conversion.settable_sens, conversion.sens, conversion.cm_per360, conversion.error_percent
+        ),
+        json: serde_json::to_value(&conversion).map_err(|e| e.to_string())?,
+    })
+}
+
+fn scoped_counts_command(args: &CliArgs, storage: &StorageContext) -> Result<CliOutput, String> {
+    let cm_per360 = args.positional_number(0, "cm/360")?;
+    let (dpi_correction, _) = app_corrections(storage)?;
+
+    let counts = calculate_scoped_counts(
+        cm_per360,
+        args.number("dpi")?,
+        dpi_correction,
+        args.number("fov")?,
+        args.number("scoped-fov")?,
+    );
+    Ok(CliOutput {
+        text: format!("{} counts", counts),
+        json: json!({ "counts": counts }),
+    })
+}
+
+fn fov_command(args: &CliArgs) -> Result<CliOutput, String> {
+    let fov = args.positional_number(0, "FOV")?;
+    let parse_scale = |name: &str| {
+        serde_json::from_value::<FovScale>(json!(name)).map_err(|_| format!("Unknown FOV scale: {}", name))
+    };
+    let from = parse_scale(args.option("from")?)?;
+    let to = parse_scale(args.option("to")?)?;
+
+    let aspect = args.options.get("aspect").map(String::as_str).unwrap_or("16:9");
+    let (width, height) = aspect
+        .split_once(':')
+        .and_then(|(w, h)| Some((w.parse::<f64>().ok()?, h.parse::<f64>().ok()?)))
+        .ok_or_else(|| format!("Aspect ratio must look like 16:9, got {}", aspect))?;
+
+    let converted = horizontal_to_fov(fov_to_horizontal(fov, from, width, height), to, width, height);
+    Ok(CliOutput {
+        text: format!("{:.4}", converted),
+        json: json!({ "fov": converted }),
+    })
+}
+
+fn history_command(args: &CliArgs, storage: &StorageContext) -> Result<CliOutput, String> {
+    let option = |name: &str| args.options.get(name).cloned();
+    let number = |name: &str| -> Result<Option<u64>, String> {
+        option(name)
+            .map(|value| value.parse().map_err(|_| format!("--{} must be a number", name)))
+            .transpose()
+    };
+
+    let filter = HistoryFilter {
+        kind: option("kind")
+            .map(|kind| serde_json::from_value(json!(kind)).map_err(|_| format!("Unknown measurement kind: {}", kind)))
+            .transpose()?,
+        game: option("game"),
+        device: option("device"),
+        since: number("since")?,
+        until: number("until")?,
+        limit: number("limit")?.map(|limit| limit as usize),
+    };
+    let format = match option("format").as_deref() {
+        None | Some("csv") => TableFormat::Csv,
+        Some("markdown") => TableFormat::Markdown,
+        Some(format) => return Err(format!("Unknown format: {}", format)),
+    };
+
+    let records = load_history(&storage.history_path()).map_err(|e| e.to_string())?;
+    let records = filter_history(&records, &filter);
+    Ok(CliOutput {
+        text: format_history(&records, format).trim_end().to_string(),
+        json: serde_json::to_value(&records).map_err(|e| e.to_string())?,
+    })
+}
+
+fn games_command(args: &CliArgs, storage: &StorageContext) -> Result<CliOutput, String> {
+    let action = args.positional.first().map(String::as_str).unwrap_or("list");
+    let name = args.positional.get(1);
+    let path = storage.games_path();
+
+    match action {
+        "list" => {
+            let games = load_games(storage)?;
+            let text = games
+                .iter()
+                .map(|game| format!("{}: {}", game.name, game.yaw))
+                .collect::<Vec<_>>()
+                .join("\n");
+            Ok(CliOutput {
+                text,
+                json: serde_json::to_value(&games).map_err(|e| e.to_string())?,
+            })
+        }
+        "add" => {
+            let name = name.ok_or("Missing game name")?;
+            let mut games = load_games(storage)?;
+            if games.iter().any(|game| &game.name == name) {
+                return Err(format!("{} already exists", name));
+            }
+
+            let game: GameYaw = serde_json::from_value(json!({
+                "name": name,
+                "yaw": args.number::<f64>("yaw")?,
+                "raw_input": !args.flag("no-raw-input"),
+            }))
+            .map_err(|e| e.to_string())?;
+            games.push(game);
+            save_yaw_data(&path, &games).map_err(|e| e.to_string())?;
+
+            Ok(CliOutput {
+                text: format!("Added {}", name),
+                json: json!({ "added": name }),
+            })
+        }
+        "remove" => {
+            let name = name.ok_or("Missing game name")?;
+            let mut games = load_games(storage)?;
+            let count = games.len();
+            games.retain(|game| &game.name != name);
+            if games.len() == count {
+                return Err(format!("Unknown game: {}", name));
+            }
+            save_yaw_data(&path, &games).map_err(|e| e.to_string())?;
+
+            Ok(CliOutput {
+                text: format!("Removed {}", name),
+                json: json!({ "removed": name }),
+            })
+        }
+        "export" => {
+            let file = name.ok_or("Missing file")?;
+            let games = load_games(storage)?;
+            let data = serde_json::to_string_pretty(&games).map_err(|e| e.to_string())?;
+            fs::write(file, data).map_err(|e| e.to_string())?;
+
+            Ok(CliOutput {
+                text: format!("Exported {} games to {}", games.len(), file),
+                json: json!({ "exported": games.len(), "file": file }),
+            })
+        }
+        "import" => {
+            let file = name.ok_or("Missing file")?;
+            let imported = load_yaw_data(Path::new(file)).map_err(|e| e.to_string())?;
+            // Imported entries win over saved ones with the same name, --replace drops everything else
+            let mut games = if args.flag("replace") { Vec::new() } else { load_games(storage)? };
+            games.retain(|game| !imported.iter().any(|new| new.name == game.name));
+            games.extend(imported.iter().cloned());
+            save_yaw_data(&path, &games).map_err(|e| e.to_string())?;
+
+            Ok(CliOutput {
+                text: format!("Imported {} games", imported.len()),
+                json: json!({ "imported": imported.len() }),
+            })
+        }
+        "check" => {
+            let file = name.map(PathBuf::from).unwrap_or(path);
+            let games = load_yaw_data(&file).map_err(|e| format!("{}: {}", file.display(), e))?;
+            let problems = check_games(&games);
+            if problems.is_empty() {
+                Ok(CliOutput {
+                    text: format!("{} games OK", games.len()),
+                    json: json!({ "games": games.len(), "problems": problems }),
+                })
+            } else {
+                Err(problems.join("\n"))
+            }
+        }
+        _ => Err(format!("Unknown games action: {}", action)),
+    }
+}
+
+// Mistakes that would make conversions wrong without failing to load
+fn check_games(games: &[GameYaw]) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    for (index, game) in games.iter().enumerate() {
+        if game.name.trim().is_empty() {
+            problems.push(format!("Entry {} has no name", index));
+        }
+        if game.yaw.is_nan() || game.yaw <= 0.0 {
+            problems.push(format!("{}: yaw must be greater than zero", game.name));
+        }
+        if games[..index].iter().any(|other| other.name == game.name) {
+            problems.push(format!("{}: duplicate name", game.name));
+        }
+        if let (Some(min), Some(max)) = (game.granularity.sens_min, game.granularity.sens_max) {
+            if min > max {
+                problems.push(format!("{}: sens_min is above sens_max", game.name));
+            }
+        }
+        if let Some(curve) = &game.yaw_curve {
+            if curve.windows(2).any(|points| points[1].0 <= points[0].0) {
+                problems.push(format!("{}: yaw_curve must be sorted by sensitivity", game.name));
+            }
+        }
+    }
+
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        std::iter::once("aimcalibrate").chain(args.iter().copied()).map(String::from).collect()
+    }
+
+    fn write_games(path: &Path, games: Value) {
+        fs::write(path, games.to_string()).unwrap();
+    }
+
+    #[test]
+    fn parse_splits_positional_options_and_flags() {
+        let parsed = CliArgs::parse(&args(&["400", "--dpi", "800", "--json", "--game", "CS2"])[1..]).unwrap();
+
+        assert_eq!(parsed.positional, vec!["400"]);
+        assert_eq!(parsed.option("dpi"), Ok("800"));
+        assert_eq!(parsed.number::<i32>("dpi"), Ok(800));
+        assert_eq!(parsed.option("game"), Ok("CS2"));
+        assert!(parsed.flag("json"));
+        assert!(!parsed.flag("replace"));
+        assert_eq!(parsed.positional_number(0, "sens"), Ok(400.0));
+    }
+
+    #[test]
+    fn parse_reports_bad_arguments() {
+        assert_eq!(
+            CliArgs::parse(&args(&["--dpi"])[1..]).err(),
+            Some("Missing value for --dpi".to_string())
+        );
+
+        let parsed = CliArgs::parse(&args(&["abc", "--dpi", "high"])[1..]).unwrap();
+        assert_eq!(parsed.number::<i32>("dpi"), Err("--dpi must be a number".to_string()));
+        assert_eq!(parsed.option("yaw"), Err("Missing --yaw".to_string()));
+        assert_eq!(parsed.positional_number(0, "sens"), Err("sens must be a number".to_string()));
+        assert_eq!(parsed.positional_number(1, "dpi"), Err("Missing dpi".to_string()));
+    }
+
+    #[test]
+    fn only_commands_run_the_cli() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = StorageContext::new(dir.path().to_path_buf(), false);
+
+        assert_eq!(run_cli(&args(&[]), &storage), None);
+        assert_eq!(run_cli(&args(&["--minimized"]), &storage), None);
+        assert_eq!(run_cli(&args(&["help"]), &storage), Some(0));
+        assert_eq!(run_cli(&args(&["--help"]), &storage), Some(0));
+        assert_eq!(run_cli(&args(&["-h"]), &storage), Some(0));
+        assert_eq!(run_cli(&args(&["cm360", "--help"]), &storage), Some(0));
+        assert_eq!(run_cli(&args(&["cm360", "--dpi"]), &storage), Some(2));
+        assert_eq!(run_cli(&args(&["cm360", "abc", "--dpi", "800", "--yaw", "0.022"]), &storage), Some(1));
+    }
+
+    #[test]
+    fn games_check_exit_codes() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = StorageContext::new(dir.path().join("config"), false);
+        let good = dir.path().join("good.json");
+        let bad = dir.path().join("bad.json");
+        let broken = dir.path().join("broken.json");
+        write_games(&good, json!([{ "name": "CS2", "yaw": 0.022 }, { "name": "Valorant", "yaw": 0.07 }]));
+        write_games(&bad, json!([{ "name": "CS2", "yaw": 0.022 }, { "name": "CS2", "yaw": 0.0 }]));
+        fs::write(&broken, "[{").unwrap();
+
+        assert_eq!(run_cli(&args(&["games", "check", good.to_str().unwrap()]), &storage), Some(0));
+        assert_eq!(run_cli(&args(&["games", "check", bad.to_str().unwrap()]), &storage), Some(1));
+        assert_eq!(run_cli(&args(&["games", "check", broken.to_str().unwrap()]), &storage), Some(1));
+        // A missing Games.json is an empty list
+        assert_eq!(run_cli(&args(&["games", "check"]), &storage), Some(0));
+        assert!(!storage.config_dir.exists());
+    }
+
+    #[test]
+    fn check_games_lists_every_problem() {
+        let games: Vec<GameYaw> = serde_json::from_value(json!([
+            { "name": "", "yaw": 0.022 },
+            { "name": "A", "yaw": -1.0 },
+            { "name": "A", "yaw": 0.022, "yaw_curve": [[2.0, 0.02], [1.0, 0.03]] },
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            check_games(&games),
+            vec![
+                "Entry 0 has no name",
+                "A: yaw must be greater than zero",
+                "A: duplicate name",
+                "A: yaw_curve must be sorted by sensitivity",
+            ]
+        );
+    }
+
+    #[test]
+    fn reading_commands_leave_the_config_folder_alone() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = StorageContext::new(dir.path().join("config"), false);
+
+        assert_eq!(run_cli(&args(&["games", "list"]), &storage), Some(0));
+        assert_eq!(run_cli(&args(&["cm360", "1", "--dpi", "800", "--yaw", "0.022"]), &storage), Some(0));
+        assert_eq!(run_cli(&args(&["history"]), &storage), Some(0));
+        assert!(!storage.config_dir.exists());
+
+        assert_eq!(run_cli(&args(&["games", "add", "CS2", "--yaw", "0.022"]), &storage), Some(0));
+        assert_eq!(load_games(&storage).unwrap().len(), 1);
+    }
+}
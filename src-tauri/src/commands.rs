@@ -1,10 +1,25 @@
-use crate::calculations::{calculate_cm, calculate_sens, convert_sensitivity};
-use crate::models::{AppSettings, GameYaw, UserSettings, YawStuff};
+use aimcalibrate_core::calculations::{calculate_angular_resolution, convert_unit, pointer_speed_multiplier};
+use aimcalibrate_core::models::{AccelConversion, AccelCurve, AngularResolution, AppSettings, CalibrationPlan, CalibrationWizard, DpiCandidate, DpiStuff, FovScale, GameConfig, GameConfigValues, GameList, GamepadConversion, GameSensResult, GameYaw, HistoryFilter, InstalledGame, MeasurementKind, MeasurementRecord, MovementTrace, ScopeConvention, ScopeTable, SensConversion, SensFinder, SensGranularity, SensUnit, StorageIssue, TableFormat, TraceReplay, UserSettings, WizardStep, YawStuff};
 #[cfg(not(target_os = "windows"))]
 use crate::mouse_tracker_mock::AppState;
-use crate::utils::{get_yaw_file_path, load_yaw_data, save_app_settings, save_yaw_data, setup_global_shortcuts};
+use aimcalibrate_core::acceleration::{convert_accel_curve, curve_samples};
+use aimcalibrate_core::batch::{convert_to_all_games, format_table};
+use aimcalibrate_core::discovery::{default_steam_roots, discover_games, resolve_config_path};
+use aimcalibrate_core::game_config::{read_config_values, write_config_sens};
+use aimcalibrate_core::gamepad::convert_gamepad_sensitivity;
+use aimcalibrate_core::history::{filter_history, format_history, new_record};
+use aimcalibrate_core::measurement::{measured_dpi_correction, reset_dpi_measurement, set_dpi_measurement, set_yaw_sens};
+use aimcalibrate_core::optimizer::optimize_dpi;
+use aimcalibrate_core::sens_finder::{choose_candidate, new_sens_finder, undo_choice};
+use aimcalibrate_core::scopes::{calculate_scope_table, monitor_distance_ratio, scope_model, ScopeContext, DEFAULT_ASPECT_RATIO};
+use aimcalibrate_core::traces::{create_trace, replay_trace};
+use aimcalibrate_core::wizard::{advance_wizard, finish_wizard, new_wizard, wizard_game};
+use aimcalibrate_core::storage::{list_traces, load_history, load_trace, save_trace, save_yaw_data, write_with_backup, StorageContext};
+use crate::api_server::{generate_api_token, start_api_server};
+use crate::utils::{clear_storage_issues, emit_update, record_measurement, save_app_settings, setup_global_shortcuts, storage_issues};
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
-use tauri::{AppHandle, State};
+use tauri::{AppHandle, Manager, State};
 #[cfg(target_os = "windows")]
 use crate::mouse_tracker::AppState;
 
@@ -12,21 +27,53 @@ use crate::mouse_tracker::AppState;
 pub fn set_app_settings(
     turn_speed: Option<f32>,
     hotkeys: Option<Vec<String>>,
+    pointer_speed: Option<u32>,
+    game_folders: Option<Vec<String>>,
     state: State<'_, Arc<Mutex<AppSettings>>>,
     app_handle: AppHandle,
+    storage: State<'_, StorageContext>,
 ) {
     {
         let mut params = state.lock().unwrap();
 
         params.turn_speed = turn_speed.unwrap_or(params.turn_speed);
         params.hotkeys = hotkeys.unwrap_or(params.hotkeys.clone());
+        params.pointer_speed = pointer_speed.unwrap_or(params.pointer_speed).clamp(1, 20);
+        params.game_folders = game_folders.unwrap_or(params.game_folders.clone());
     }
 
-    save_app_settings(state.clone()).expect("Failed to save Settings");
+    save_app_settings(&storage, state.clone()).expect("Failed to save Settings");
 
     setup_global_shortcuts(app_handle);
 }
 
+#[tauri::command]
+pub fn set_api_settings(
+    enabled: Option<bool>,
+    port: Option<u16>,
+    new_token: Option<bool>,
+    state: State<'_, Arc<Mutex<AppSettings>>>,
+    app_handle: AppHandle,
+    storage: State<'_, StorageContext>,
+) -> Result<AppSettings, String> {
+    let settings = {
+        let mut params = state.lock().unwrap();
+
+        params.api_enabled = enabled.unwrap_or(params.api_enabled);
+        params.api_port = port.unwrap_or(params.api_port);
+        if new_token.unwrap_or(false) || (params.api_enabled && params.api_token.is_empty()) {
+            params.api_token = generate_api_token()?;
+        }
+        params.clone()
+    };
+
+    save_app_settings(&storage, state).map_err(|e| e.to_string())?;
+
+    start_api_server(app_handle);
+
+    Ok(settings)
+}
+
 #[tauri::command]
 pub fn get_app_settings(state: State<'_, Arc<Mutex<AppSettings>>>) -> AppSettings {
     state.lock().unwrap().clone()
@@ -36,10 +83,8 @@ pub fn get_app_settings(state: State<'_, Arc<Mutex<AppSettings>>>) -> AppSetting
 pub fn set_yaw_values(sens: Option<f64>, state: State<'_, Arc<Mutex<YawStuff>>>) -> YawStuff {
     let mut params = state.lock().unwrap();
 
-    params.sens = sens.unwrap_or(params.sens);
-    params.yaw = params.inc / params.sens;
-    params.lower_limit = params.yaw * 0.9;
-    params.upper_limit = params.yaw * 1.1;
+    let sens = sens.unwrap_or(params.sens);
+    set_yaw_sens(&mut params, sens);
 
     params.clone()
 }
@@ -50,31 +95,107 @@ pub fn get_yaw_values(state: State<'_, Arc<Mutex<YawStuff>>>) -> YawStuff {
 }
 
 #[tauri::command]
-pub fn save_game_yaw(name: String, yaw: Option<f64>, state: State<'_, Arc<Mutex<YawStuff>>>) {
+pub fn save_game_yaw(
+    name: String,
+    yaw: Option<f64>,
+    raw_input: Option<bool>,
+    state: State<'_, Arc<Mutex<YawStuff>>>,
+    settings: State<'_, Arc<Mutex<AppSettings>>>,
+    game_list: State<'_, Arc<Mutex<GameList>>>,
+    app_handle: AppHandle,
+) -> Result<(), String> {
+    // Taken before the yaw state, in the same order as the hotkey handler locks them
+    let profile = app_handle.state::<Arc<Mutex<UserSettings>>>().lock().unwrap().clone();
+    let (pointer_speed, dpi_correction) = {
+        let settings = settings.lock().unwrap();
+        (settings.pointer_speed, settings.dpi_correction)
+    };
     let params = state.lock().unwrap();
     let mut game_yaw = GameYaw {
         name,
         yaw: params.yaw,
+        yaw_curve: None,
+        raw_input: raw_input.unwrap_or(true),
+        granularity: SensGranularity::default(),
+        scopes: Vec::new(),
+        scope_convention: ScopeConvention::default(),
+        gamepad: None,
+        config: None,
+        steam_app_id: None,
     };
 
     if yaw.is_some() {
         game_yaw.yaw = yaw.unwrap();
     }
 
-    let path = get_yaw_file_path();
-    let mut game_yaws = load_yaw_data(&path).unwrap_or_default();
+    // The turns used for measuring went through the pointer speed, so take it back out of the yaw
+    if !game_yaw.raw_input {
+        game_yaw.yaw /= pointer_speed_multiplier(pointer_speed);
+    }
+
+    let mut list = game_list.lock().unwrap();
+    if let Some(error) = &list.error {
+        return Err(format!("Fix Games.json before saving: {}", error));
+    }
+
+    let mut record = new_record(
+        MeasurementKind::Yaw,
+        "saved",
+        params.counts,
+        game_yaw.yaw,
+        None,
+        &profile,
+        dpi_correction,
+    );
+    record.game = Some(game_yaw.name.clone());
 
+    let mut game_yaws = list.games.clone();
     game_yaws.push(game_yaw);
 
-    save_yaw_data(&path, &game_yaws).expect("Failed to save yaw data");
+    save_yaw_data(&app_handle.state::<StorageContext>().games_path(), &game_yaws).map_err(|e| e.to_string())?;
+    list.games = game_yaws;
+    record_measurement(&app_handle, record);
     println!("Game yaw data saved successfully.");
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_games(game_list: State<'_, Arc<Mutex<GameList>>>) -> Vec<GameYaw> {
+    game_list.lock().unwrap().games.clone()
+}
+
+#[tauri::command]
+pub fn get_measurement_history(
+    filter: Option<HistoryFilter>,
+    storage: State<'_, StorageContext>,
+) -> Result<Vec<MeasurementRecord>, String> {
+    let records = load_history(&storage.history_path()).map_err(|e| e.to_string())?;
+    Ok(filter_history(&records, &filter.unwrap_or_default()))
+}
+
+#[tauri::command]
+pub fn export_measurement_history(
+    filter: Option<HistoryFilter>,
+    format: TableFormat,
+    storage: State<'_, StorageContext>,
+) -> Result<String, String> {
+    let records = load_history(&storage.history_path()).map_err(|e| e.to_string())?;
+    Ok(format_history(&filter_history(&records, &filter.unwrap_or_default()), format))
 }
 
 #[tauri::command]
-pub fn get_games() -> Vec<GameYaw> {
-    let path = get_yaw_file_path();
-    let games = load_yaw_data(&path).expect("No Games found");
-    games
+pub fn get_storage_context(storage: State<'_, StorageContext>) -> StorageContext {
+    storage.inner().clone()
+}
+
+#[tauri::command]
+pub fn get_storage_issues() -> Vec<StorageIssue> {
+    storage_issues()
+}
+
+#[tauri::command]
+pub fn dismiss_storage_issues() {
+    clear_storage_issues();
 }
 
 #[tauri::command]
@@ -97,6 +218,12 @@ pub fn set_user_settings(
     params.game_fov = game_fov.unwrap_or(params.game_fov);
 }
 
+// Game the next measurements are recorded for, an empty name clears it
+#[tauri::command]
+pub fn set_measured_game(game: Option<String>, state: State<'_, Arc<Mutex<UserSettings>>>) {
+    state.lock().unwrap().game = game.filter(|game| !game.is_empty());
+}
+
 #[tauri::command]
 pub fn get_initial_values(state: State<'_, Arc<Mutex<UserSettings>>>) -> UserSettings {
     state.lock().unwrap().clone()
@@ -109,13 +236,573 @@ pub fn set_current_page(page: String, state: State<'_, Arc<Mutex<AppState>>>) {
 }
 
 #[tauri::command]
-pub fn convert_sens(dpi: i32, sens: f64, yaw1: f64, new_dpi: i32, yaw2: f64) -> f64 {
-    let mut new_sens = convert_sensitivity(sens, dpi, new_dpi, yaw1, yaw2);
-    if yaw1 == 360.0 {
-        new_sens = calculate_sens(sens, new_dpi, yaw2);
+pub fn convert_sens(
+    value: f64,
+    from: SensUnit,
+    dpi: i32,
+    to: SensUnit,
+    new_dpi: i32,
+    settings: State<'_, Arc<Mutex<AppSettings>>>,
+) -> Result<SensConversion, String> {
+    let (dpi_correction, pointer_speed) = {
+        let settings = settings.lock().unwrap();
+        (settings.dpi_correction, settings.pointer_speed)
+    };
+
+    convert_unit(value, &from, dpi, &to, new_dpi, dpi_correction, pointer_speed)
+}
+
+#[tauri::command]
+pub fn set_dpi_values(dpi: Option<i32>, distance: Option<f64>, state: State<'_, Arc<Mutex<DpiStuff>>>) -> DpiStuff {
+    let mut params = state.lock().unwrap();
+
+    let dpi = dpi.unwrap_or(params.dpi);
+    let distance = distance.unwrap_or(params.distance);
+    set_dpi_measurement(&mut params, dpi, distance);
+
+    params.clone()
+}
+
+#[tauri::command]
+pub fn get_dpi_values(state: State<'_, Arc<Mutex<DpiStuff>>>) -> DpiStuff {
+    state.lock().unwrap().clone()
+}
+
+#[tauri::command]
+pub fn reset_dpi_values(state: State<'_, Arc<Mutex<DpiStuff>>>) -> DpiStuff {
+    let mut params = state.lock().unwrap();
+
+    reset_dpi_measurement(&mut params);
+
+    params.clone()
+}
+
+#[tauri::command]
+pub fn save_dpi_correction(
+    dpi_state: State<'_, Arc<Mutex<DpiStuff>>>,
+    state: State<'_, Arc<Mutex<AppSettings>>>,
+    storage: State<'_, StorageContext>,
+) -> Result<f64, String> {
+    let correction = measured_dpi_correction(&dpi_state.lock().unwrap())
+        .ok_or_else(|| "No DPI measurement to save".to_string())?;
+
+    state.lock().unwrap().dpi_correction = correction;
+    save_app_settings(&storage, state.clone()).map_err(|e| e.to_string())?;
+
+    Ok(correction)
+}
+
+#[tauri::command]
+pub fn clear_dpi_correction(
+    state: State<'_, Arc<Mutex<AppSettings>>>,
+    storage: State<'_, StorageContext>,
+) -> Result<(), String> {
+    state.lock().unwrap().dpi_correction = 1.0;
+    save_app_settings(&storage, state.clone()).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn save_movement_trace(
+    name: String,
+    state: State<'_, Arc<Mutex<AppState>>>,
+    user_state: State<'_, Arc<Mutex<UserSettings>>>,
+    settings_state: State<'_, Arc<Mutex<AppSettings>>>,
+    yaw_state: State<'_, Arc<Mutex<YawStuff>>>,
+    storage: State<'_, StorageContext>,
+) -> Result<String, String> {
+    let app_state = state.lock().unwrap();
+    if app_state.tracker.tracking {
+        return Err("Stop tracking before saving the trace".to_string());
+    }
+    if app_state.tracker.packets.is_empty() {
+        return Err("No tracking session recorded yet".to_string());
+    }
+
+    let trace = create_trace(
+        &app_state.tracker.packets,
+        app_state.tracker.device.clone(),
+        app_state.tracker.page.clone(),
+        user_state.lock().unwrap().clone(),
+        settings_state.lock().unwrap().dpi_correction,
+        yaw_state.lock().unwrap().sens,
+    );
+
+    let path = storage.trace_path(&name);
+    save_trace(&path, &trace).map_err(|e| e.to_string())?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+pub fn get_traces(storage: State<'_, StorageContext>) -> Result<Vec<String>, String> {
+    list_traces(&storage.traces_dir()).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn load_movement_trace(name: String, storage: State<'_, StorageContext>) -> Result<MovementTrace, String> {
+    load_trace(&storage.trace_path(&name)).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn replay_movement_trace(
+    name: String,
+    cm360: Option<f64>,
+    dpi: Option<i32>,
+    game_sens: Option<f64>,
+    yaw_sens: Option<f64>,
+    storage: State<'_, StorageContext>,
+) -> Result<TraceReplay, String> {
+    let mut trace = load_trace(&storage.trace_path(&name)).map_err(|e| e.to_string())?;
+
+    trace.settings.cm360 = cm360.unwrap_or(trace.settings.cm360);
+    trace.settings.dpi = dpi.unwrap_or(trace.settings.dpi);
+    trace.settings.game_sens = game_sens.unwrap_or(trace.settings.game_sens);
+    trace.yaw_sens = yaw_sens.unwrap_or(trace.yaw_sens);
+
+    Ok(replay_trace(&trace))
+}
+
+#[tauri::command]
+pub fn optimize_game_dpi(
+    cm360: f64,
+    dpi_steps: Vec<i32>,
+    games: Vec<String>,
+    limit: Option<usize>,
+    settings: State<'_, Arc<Mutex<AppSettings>>>,
+    game_list: State<'_, Arc<Mutex<GameList>>>,
+) -> Result<Vec<DpiCandidate>, String> {
+    let all_games = game_list.lock().unwrap().games.clone();
+    let mut selected = Vec::new();
+    for name in &games {
+        match all_games.iter().find(|game| &game.name == name) {
+            Some(game) => selected.push(game.clone()),
+            None => return Err(format!("Unknown game: {}", name)),
+        }
     }
-    if yaw2 == 360.0 {
-        new_sens = calculate_cm(sens, dpi, yaw1);
+
+    let (dpi_correction, pointer_speed) = {
+        let settings = settings.lock().unwrap();
+        (settings.dpi_correction, settings.pointer_speed)
+    };
+
+    let mut candidates = optimize_dpi(cm360, &dpi_steps, &selected, dpi_correction, pointer_speed)?;
+    if let Some(limit) = limit {
+        candidates.truncate(limit);
+    }
+    Ok(candidates)
+}
+
+#[tauri::command]
+pub fn analyze_angular_resolution(
+    sens: f64,
+    yaw: f64,
+    fov: f64,
+    fov_scale: Option<FovScale>,
+    width: u32,
+    height: u32,
+) -> Result<AngularResolution, String> {
+    if width == 0 || height == 0 {
+        return Err("Screen resolution must not be zero".to_string());
+    }
+    if fov <= 0.0 || fov >= 180.0 {
+        return Err("FOV must be between 0 and 180 degrees".to_string());
     }
-    new_sens
-}
\ No newline at end of file
+
+    Ok(calculate_angular_resolution(
+        sens,
+        yaw,
+        fov,
+        fov_scale.unwrap_or(FovScale::Horizontal),
+        width as f64,
+        height as f64,
+    ))
+}
+
+#[tauri::command]
+pub fn convert_to_all(
+    source_game: String,
+    sens: f64,
+    dpi: i32,
+    new_dpi: i32,
+    settings: State<'_, Arc<Mutex<AppSettings>>>,
+    game_list: State<'_, Arc<Mutex<GameList>>>,
+) -> Result<Vec<GameSensResult>, String> {
+    let games = game_list.lock().unwrap().games.clone();
+    let source = games
+        .iter()
+        .find(|game| game.name == source_game)
+        .ok_or(format!("Unknown game: {}", source_game))?;
+
+    let (dpi_correction, pointer_speed) = {
+        let settings = settings.lock().unwrap();
+        (settings.dpi_correction, settings.pointer_speed)
+    };
+
+    convert_to_all_games(source, sens, dpi, new_dpi, &games, dpi_correction, pointer_speed)
+}
+
+#[tauri::command]
+pub fn export_conversion_table(results: Vec<GameSensResult>, format: TableFormat) -> String {
+    format_table(&results, format)
+}
+
+#[tauri::command]
+pub fn get_scope_table(
+    game: String,
+    cm360: f64,
+    dpi: i32,
+    hipfire_fov: f64,
+    monitor_distance: Option<f64>,
+    settings: State<'_, Arc<Mutex<AppSettings>>>,
+    game_list: State<'_, Arc<Mutex<GameList>>>,
+) -> Result<ScopeTable, String> {
+    let games = game_list.lock().unwrap().games.clone();
+    let game = games
+        .iter()
+        .find(|g| g.name == game)
+        .ok_or(format!("Unknown game: {}", game))?;
+
+    let (dpi_correction, pointer_speed) = {
+        let settings = settings.lock().unwrap();
+        (settings.dpi_correction, settings.pointer_speed)
+    };
+
+    calculate_scope_table(
+        game,
+        cm360,
+        dpi,
+        hipfire_fov,
+        monitor_distance.unwrap_or(0.0),
+        dpi_correction,
+        pointer_speed,
+    )
+}
+
+#[tauri::command]
+pub fn calculate_scope_sens(
+    convention: ScopeConvention,
+    hipfire_fov: f64,
+    scoped_fov: f64,
+    hipfire_sens: Option<f64>,
+    monitor_distance: Option<f64>,
+) -> f64 {
+    let context = ScopeContext {
+        hipfire_fov,
+        scoped_fov,
+        hipfire_sens: hipfire_sens.unwrap_or(1.0),
+        aspect_ratio: DEFAULT_ASPECT_RATIO,
+    };
+    let ratio = monitor_distance_ratio(hipfire_fov, scoped_fov, monitor_distance.unwrap_or(0.0));
+
+    scope_model(convention).scope_value(ratio, &context)
+}
+
+#[tauri::command]
+pub fn convert_gamepad_sens(
+    source_game: String,
+    sens: f64,
+    dest_game: String,
+    deflection: Option<f64>,
+    held: Option<f64>,
+    mouse_speed: Option<f64>,
+    game_list: State<'_, Arc<Mutex<GameList>>>,
+) -> Result<GamepadConversion, String> {
+    let games = game_list.lock().unwrap().games.clone();
+    let profile = |name: &String| {
+        games
+            .iter()
+            .find(|game| &game.name == name)
+            .ok_or(format!("Unknown game: {}", name))?
+            .gamepad
+            .clone()
+            .ok_or(format!("No gamepad settings for {}", name))
+    };
+
+    let from = profile(&source_game)?;
+    let to = profile(&dest_game)?;
+
+    Ok(convert_gamepad_sensitivity(
+        &from,
+        sens,
+        &to,
+        deflection.unwrap_or(1.0),
+        held.unwrap_or(0.0),
+        mouse_speed.unwrap_or(10.0),
+    ))
+}
+
+#[tauri::command]
+pub fn get_accel_curve_samples(
+    curve: AccelCurve,
+    sens: f64,
+    dpi: i32,
+    yaw: f64,
+    max_speed: Option<f64>,
+    points: Option<usize>,
+    settings: State<'_, Arc<Mutex<AppSettings>>>,
+) -> Vec<(f64, f64)> {
+    let dpi_correction = settings.lock().unwrap().dpi_correction;
+    curve_samples(&curve, sens, dpi, yaw, dpi_correction, max_speed.unwrap_or(100.0), points.unwrap_or(50))
+}
+
+#[tauri::command]
+pub fn convert_accel(
+    curve: AccelCurve,
+    sens: f64,
+    dpi: i32,
+    yaw1: f64,
+    new_dpi: i32,
+    yaw2: f64,
+    settings: State<'_, Arc<Mutex<AppSettings>>>,
+) -> Result<AccelConversion, String> {
+    if dpi <= 0 || new_dpi <= 0 {
+        return Err("DPI must be greater than zero".to_string());
+    }
+
+    let dpi_correction = settings.lock().unwrap().dpi_correction;
+    let (new_sens, new_curve) = convert_accel_curve(&curve, sens, dpi, yaw1, new_dpi, yaw2, dpi_correction);
+    let samples = curve_samples(&new_curve, new_sens, new_dpi, yaw2, dpi_correction, 100.0, 50);
+
+    Ok(AccelConversion {
+        sens: new_sens,
+        curve: new_curve,
+        samples,
+    })
+}
+
+#[tauri::command]
+pub fn start_sens_finder(
+    lower_limit: f64,
+    upper_limit: f64,
+    yaw: f64,
+    dpi: i32,
+    state: State<'_, Arc<Mutex<SensFinder>>>,
+    settings: State<'_, Arc<Mutex<AppSettings>>>,
+) -> Result<SensFinder, String> {
+    if lower_limit <= 0.0 || upper_limit <= 0.0 || dpi <= 0 || yaw <= 0.0 {
+        return Err("cm/360 range, yaw and DPI must be greater than zero".to_string());
+    }
+
+    let dpi_correction = settings.lock().unwrap().dpi_correction;
+    let mut finder = state.lock().unwrap();
+    *finder = new_sens_finder(lower_limit, upper_limit, yaw, dpi, dpi_correction);
+    Ok(finder.clone())
+}
+
+#[tauri::command]
+pub fn get_sens_finder(state: State<'_, Arc<Mutex<SensFinder>>>) -> SensFinder {
+    state.lock().unwrap().clone()
+}
+
+#[tauri::command]
+pub fn choose_sens(
+    choice: usize,
+    state: State<'_, Arc<Mutex<SensFinder>>>,
+    settings: State<'_, Arc<Mutex<AppSettings>>>,
+) -> SensFinder {
+    let dpi_correction = settings.lock().unwrap().dpi_correction;
+    let mut finder = state.lock().unwrap();
+    choose_candidate(&mut finder, choice, dpi_correction);
+    finder.clone()
+}
+
+#[tauri::command]
+pub fn undo_sens_choice(
+    state: State<'_, Arc<Mutex<SensFinder>>>,
+    settings: State<'_, Arc<Mutex<AppSettings>>>,
+) -> SensFinder {
+    let dpi_correction = settings.lock().unwrap().dpi_correction;
+    let mut finder = state.lock().unwrap();
+    undo_choice(&mut finder, dpi_correction);
+    finder.clone()
+}
+
+#[tauri::command]
+pub fn start_calibration_wizard(
+    plan: CalibrationPlan,
+    state: State<'_, Arc<Mutex<CalibrationWizard>>>,
+    yaw_state: State<'_, Arc<Mutex<YawStuff>>>,
+    game_list: State<'_, Arc<Mutex<GameList>>>,
+    app_handle: AppHandle,
+) -> Result<CalibrationWizard, String> {
+    if plan.game.trim().is_empty() {
+        return Err("Enter a name for the game".to_string());
+    }
+    if plan.sens <= 0.0 {
+        return Err("Sensitivity must be greater than zero".to_string());
+    }
+    if !plan.cm_per360.is_finite() || plan.cm_per360 <= 0.0 {
+        return Err("cm/360 must be greater than zero".to_string());
+    }
+    if plan.dpi <= 0 {
+        return Err("DPI must be greater than zero".to_string());
+    }
+    if game_list.lock().unwrap().games.iter().any(|game| game.name == plan.game) {
+        return Err(format!("{} is already in the game list", plan.game));
+    }
+
+    // The yaw is measured at the sensitivity of the plan
+    set_yaw_sens(&mut yaw_state.lock().unwrap(), plan.sens);
+
+    let mut wizard = state.lock().unwrap();
+    *wizard = new_wizard(plan);
+    emit_update(&app_handle, "wizard_update", wizard.clone());
+    Ok(wizard.clone())
+}
+
+#[tauri::command]
+pub fn get_calibration_wizard(state: State<'_, Arc<Mutex<CalibrationWizard>>>) -> CalibrationWizard {
+    state.lock().unwrap().clone()
+}
+
+// Confirms the current step. Every confirmed step is saved to the game list right away, so a
+// wizard that is cancelled halfway still keeps what was measured.
+#[tauri::command]
+pub fn advance_calibration_wizard(
+    state: State<'_, Arc<Mutex<CalibrationWizard>>>,
+    yaw_state: State<'_, Arc<Mutex<YawStuff>>>,
+    user_settings: State<'_, Arc<Mutex<UserSettings>>>,
+    settings: State<'_, Arc<Mutex<AppSettings>>>,
+    game_list: State<'_, Arc<Mutex<GameList>>>,
+    app_handle: AppHandle,
+) -> Result<CalibrationWizard, String> {
+    let profile = user_settings.lock().unwrap().clone();
+    let (pointer_speed, dpi_correction) = {
+        let settings = settings.lock().unwrap();
+        (settings.pointer_speed, settings.dpi_correction)
+    };
+    let yaw_counts = yaw_state.lock().unwrap().counts;
+    let mut wizard = state.lock().unwrap();
+
+    let mut next = wizard.clone();
+    advance_wizard(&mut next)?;
+    if next.step == WizardStep::Done {
+        finish_wizard(&mut next, dpi_correction, pointer_speed);
+    }
+
+    let game = wizard_game(&next, pointer_speed);
+    store_game(game.clone(), &app_handle.state::<StorageContext>(), &game_list)?;
+    if wizard.step == WizardStep::MeasureYaw {
+        let mut record = new_record(
+            MeasurementKind::Yaw,
+            "saved",
+            yaw_counts,
+            game.yaw,
+            None,
+            &profile,
+            dpi_correction,
+        );
+        record.game = Some(game.name);
+        record_measurement(&app_handle, record);
+    }
+
+    *wizard = next;
+    emit_update(&app_handle, "wizard_update", wizard.clone());
+    Ok(wizard.clone())
+}
+
+#[tauri::command]
+pub fn cancel_calibration_wizard(
+    state: State<'_, Arc<Mutex<CalibrationWizard>>>,
+    app_handle: AppHandle,
+) -> CalibrationWizard {
+    let mut wizard = state.lock().unwrap();
+    *wizard = CalibrationWizard::default();
+    emit_update(&app_handle, "wizard_update", wizard.clone());
+    wizard.clone()
+}
+
+// Adds the game to Games.json. An entry with the same name keeps its other settings and only
+// gets the measured values.
+fn store_game(game: GameYaw, storage: &StorageContext, game_list: &Mutex<GameList>) -> Result<(), String> {
+    let mut list = game_list.lock().unwrap();
+    if let Some(error) = &list.error {
+        return Err(format!("Fix Games.json before saving: {}", error));
+    }
+
+    let mut games = list.games.clone();
+    match games.iter_mut().find(|existing| existing.name == game.name) {
+        Some(existing) => {
+            existing.yaw = game.yaw;
+            existing.raw_input = game.raw_input;
+            existing.scopes = game.scopes;
+            existing.scope_convention = game.scope_convention;
+        }
+        None => games.push(game),
+    }
+
+    save_yaw_data(&storage.games_path(), &games).map_err(|e| e.to_string())?;
+    list.games = games;
+    Ok(())
+}
+
+fn installed_games(game_folders: &[String], games: &[GameYaw]) -> Vec<InstalledGame> {
+    let steam_roots = default_steam_roots(tauri::api::path::home_dir());
+    let folders: Vec<PathBuf> = game_folders.iter().map(PathBuf::from).collect();
+    discover_games(&steam_roots, &folders, games)
+}
+
+#[tauri::command]
+pub fn discover_installed_games(
+    settings: State<'_, Arc<Mutex<AppSettings>>>,
+    game_list: State<'_, Arc<Mutex<GameList>>>,
+) -> Result<Vec<InstalledGame>, String> {
+    let game_folders = settings.lock().unwrap().game_folders.clone();
+    let games = game_list.lock().unwrap().games.clone();
+    Ok(installed_games(&game_folders, &games))
+}
+
+// Config of the game and the file it points at, looking up the install folder if the path needs it
+fn find_game_config(game: &str, game_folders: &[String], games: &[GameYaw]) -> Result<(GameConfig, PathBuf), String> {
+    let entry = games
+        .iter()
+        .find(|g| g.name == game)
+        .ok_or_else(|| format!("Unknown game: {}", game))?;
+    let config = entry
+        .config
+        .clone()
+        .ok_or_else(|| format!("No config file set for {}", game))?;
+
+    let install_dir = if config.path.starts_with("{install_dir}") {
+        installed_games(game_folders, games)
+            .into_iter()
+            .find(|installed| installed.matched.as_deref() == Some(game))
+            .map(|installed| installed.install_dir)
+    } else {
+        None
+    };
+    let path = resolve_config_path(&config.path, install_dir.as_deref())
+        .ok_or_else(|| format!("{} was not found in any game library", game))?;
+
+    Ok((config, path))
+}
+
+#[tauri::command]
+pub fn read_game_config(
+    game: String,
+    settings: State<'_, Arc<Mutex<AppSettings>>>,
+    game_list: State<'_, Arc<Mutex<GameList>>>,
+) -> Result<GameConfigValues, String> {
+    let game_folders = settings.lock().unwrap().game_folders.clone();
+    let (config, path) = find_game_config(&game, &game_folders, &game_list.lock().unwrap().games)?;
+    let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+
+    let mut values = read_config_values(&config, &content);
+    values.path = path.to_string_lossy().to_string();
+    Ok(values)
+}
+
+// Returns the path of the backup of the original file
+#[tauri::command]
+pub fn write_game_config(
+    game: String,
+    sens: f64,
+    settings: State<'_, Arc<Mutex<AppSettings>>>,
+    game_list: State<'_, Arc<Mutex<GameList>>>,
+) -> Result<String, String> {
+    let game_folders = settings.lock().unwrap().game_folders.clone();
+    let (config, path) = find_game_config(&game, &game_folders, &game_list.lock().unwrap().games)?;
+    let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let updated = write_config_sens(&config, &content, sens)?;
+    let backup = write_with_backup(&path, &updated).map_err(|e| e.to_string())?;
+    Ok(backup.to_string_lossy().to_string())
+}
@@ -17,10 +17,14 @@ use mouse_tracker::{AppState, MouseTracker, APP_STATE};
 mod mouse_tracker_mock {
     use once_cell::sync::Lazy;
     use std::sync::{Arc, Mutex};
+    use aimcalibrate_core::models::MousePacket;
 
     pub struct MouseTracker {
         pub tracking: bool,
         pub count: i32,
+        pub packets: Vec<MousePacket>,
+        pub device: Option<String>,
+        pub page: String,
     }
 
     impl MouseTracker {
@@ -28,12 +32,17 @@ mod mouse_tracker_mock {
             MouseTracker {
                 tracking: false,
                 count: 0,
+                packets: Vec::new(),
+                device: None,
+                page: String::new(),
             }
         }
 
         pub fn start_tracking(&mut self) -> Result<(), String> {
             println!("Mock start tracking on macOS");
             self.tracking = true;
+            self.count = 0;
+            self.packets.clear();
             Ok(())
         }
 
@@ -54,18 +63,33 @@ mod mouse_tracker_mock {
 #[cfg(not(target_os = "windows"))]
 use mouse_tracker_mock::{AppState, MouseTracker, APP_STATE};
 
-mod calculations;
+mod api_server;
+mod cli;
 mod utils;
 mod commands;
-mod models;
 
-use calculations::{calculate_counts, calculate_scoped_counts, calculate_yaw, estimate_fov};
-use crate::commands::{convert_sens, get_app_settings, get_games, get_initial_values, get_yaw_values, save_game_yaw, set_app_settings, set_current_page, set_user_settings, set_yaw_values};
-use crate::models::{UserSettings, YawStuff};
-use crate::utils::{load_app_settings, setup_global_shortcuts};
+use crate::commands::{advance_calibration_wizard, analyze_angular_resolution, cancel_calibration_wizard, calculate_scope_sens, choose_sens, clear_dpi_correction, convert_accel, convert_gamepad_sens, convert_sens, convert_to_all, discover_installed_games, dismiss_storage_issues, export_conversion_table, export_measurement_history, get_accel_curve_samples, get_app_settings, get_calibration_wizard, get_dpi_values, get_games, get_initial_values, get_measurement_history, get_sens_finder, get_traces, get_yaw_values, load_movement_trace, get_scope_table, get_storage_context, get_storage_issues, read_game_config, optimize_game_dpi, replay_movement_trace, reset_dpi_values, save_dpi_correction, save_game_yaw, save_movement_trace, set_api_settings, set_app_settings, set_current_page, set_dpi_values, set_measured_game, set_user_settings, set_yaw_values, start_calibration_wizard, start_sens_finder, undo_sens_choice, write_game_config};
+use aimcalibrate_core::models::{CalibrationWizard, DpiStuff, GameList, UserSettings, YawStuff};
+use aimcalibrate_core::sens_finder::new_sens_finder;
+use crate::api_server::start_api_server;
+use crate::utils::{load_app_settings, load_games, resolve_storage, setup_global_shortcuts, setup_storage_issue_events, watch_games_file};
 
 fn main() {
-    let app_settings = load_app_settings().expect("Failed to load settings");
+    let mut args: Vec<String> = std::env::args().collect();
+    let storage = match resolve_storage(&mut args) {
+        Ok(storage) => storage,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+    if let Some(code) = cli::run_cli(&args, &storage) {
+        std::process::exit(code);
+    }
+
+    let app_settings = load_app_settings(&storage).expect("Failed to load settings");
+    let games = load_games(&storage).expect("Failed to load games");
+    let sens_finder = new_sens_finder(20.0, 80.0, 0.022, 800, app_settings.dpi_correction);
     tauri::Builder::default()
         .manage(Arc::new(Mutex::new(UserSettings {
             cm360: 0.0,
@@ -74,6 +98,7 @@ fn main() {
             scoped_fov: 0.0,
             game_sens: 0.0,
             game_fov: 0.0,
+            game: None,
         })))
         .manage(Arc::new(Mutex::new(AppState {
             current_page: "main_sensitivity".to_string(),
@@ -88,6 +113,17 @@ fn main() {
             lower_limit: 0.0,
             upper_limit: 1000.0,
         })))
+        .manage(Arc::new(Mutex::new(DpiStuff {
+            dpi: 0,
+            distance: 10.0,
+            samples: Vec::new(),
+            effective_dpi: 0.0,
+            spread: 0.0,
+        })))
+        .manage(Arc::new(Mutex::new(sens_finder)))
+        .manage(Arc::new(Mutex::new(CalibrationWizard::default())))
+        .manage(Arc::new(Mutex::new(GameList { games, error: None })))
+        .manage(storage)
         .setup(|app| {
             #[cfg(target_os = "windows")]
             {
@@ -112,6 +148,9 @@ fn main() {
             }
 
             setup_global_shortcuts(app.handle());
+            setup_storage_issue_events(app.handle());
+            watch_games_file(app.handle());
+            start_api_server(app.handle());
 
             Ok(())
         })
@@ -123,15 +162,51 @@ fn main() {
         })
         .invoke_handler(tauri::generate_handler![
             set_user_settings,
+            set_measured_game,
             set_current_page,
             get_initial_values,
             set_app_settings,
+            set_api_settings,
             get_app_settings,
             set_yaw_values,
             get_yaw_values,
             save_game_yaw,
             get_games,
-            convert_sens
+            convert_sens,
+            set_dpi_values,
+            get_dpi_values,
+            reset_dpi_values,
+            save_dpi_correction,
+            clear_dpi_correction,
+            save_movement_trace,
+            get_traces,
+            load_movement_trace,
+            replay_movement_trace,
+            optimize_game_dpi,
+            analyze_angular_resolution,
+            convert_to_all,
+            export_conversion_table,
+            get_scope_table,
+            calculate_scope_sens,
+            convert_gamepad_sens,
+            get_accel_curve_samples,
+            convert_accel,
+            start_sens_finder,
+            get_sens_finder,
+            choose_sens,
+            undo_sens_choice,
+            start_calibration_wizard,
+            get_calibration_wizard,
+            advance_calibration_wizard,
+            cancel_calibration_wizard,
+            read_game_config,
+            write_game_config,
+            discover_installed_games,
+            get_storage_context,
+            get_measurement_history,
+            export_measurement_history,
+            get_storage_issues,
+            dismiss_storage_issues
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
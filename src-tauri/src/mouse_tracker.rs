@@ -2,12 +2,18 @@ use std::ptr::null_mut;
 use std::sync::{Arc, Mutex};
 use winapi::shared::minwindef::{LRESULT, UINT, WPARAM, LPARAM};
 use winapi::shared::windef::HWND;
-use winapi::um::winuser::{DefWindowProcW, RegisterRawInputDevices, RAWINPUTDEVICE, RIDEV_INPUTSINK, RIDEV_REMOVE, WM_INPUT, GetRawInputData, RID_INPUT, RAWINPUT, RAWINPUTHEADER, WM_CLOSE, WM_DESTROY};
+use winapi::um::winuser::{DefWindowProcW, RegisterRawInputDevices, RAWINPUTDEVICE, RIDEV_INPUTSINK, RIDEV_REMOVE, WM_INPUT, GetRawInputData, GetRawInputDeviceInfoW, RID_INPUT, RIDI_DEVICENAME, RAWINPUT, RAWINPUTHEADER, WM_CLOSE, WM_DESTROY};
 use std::ptr::NonNull;
+use std::time::Instant;
+use aimcalibrate_core::models::MousePacket;
 
 pub struct MouseTracker {
     pub tracking: bool,
     pub count: i32,
+    pub packets: Vec<MousePacket>,
+    pub device: Option<String>,
+    pub page: String,
+    started: Instant,
 }
 
 impl MouseTracker {
@@ -15,6 +21,10 @@ impl MouseTracker {
         MouseTracker {
             tracking: false,
             count: 0,
+            packets: Vec::new(),
+            device: None,
+            page: String::new(),
+            started: Instant::now(),
         }
     }
 
@@ -38,6 +48,9 @@ impl MouseTracker {
         if result != 0 {
             self.tracking = true;
             self.count = 0; // Reset the count when tracking starts
+            self.packets.clear();
+            self.device = None;
+            self.started = Instant::now();
             Ok(())
         } else {
             Err("Failed to register raw input device(s).".to_string())
@@ -73,6 +86,14 @@ impl MouseTracker {
         self.count += x;
     }
 
+    pub fn record_packet(&mut self, dx: i32, dy: i32) {
+        self.packets.push(MousePacket {
+            time_us: self.started.elapsed().as_micros() as u64,
+            dx,
+            dy,
+        });
+    }
+
   pub unsafe extern "system" fn window_proc(
     hwnd: HWND,
     msg: UINT,
@@ -87,8 +108,13 @@ impl MouseTracker {
             let raw_input = std::ptr::NonNull::new(lparam as *mut std::ffi::c_void).unwrap();
             match tracker.tracker.get_raw_input_data(raw_input) {
                 Ok(raw_input_data) => {
-                    let x_movement = raw_input_data.data.mouse().lLastX;
-                    tracker.tracker.update_counts(x_movement);
+                    if tracker.tracker.device.is_none() {
+                        tracker.tracker.device = MouseTracker::get_device_name(&raw_input_data.header);
+                    }
+
+                    let mouse = raw_input_data.data.mouse();
+                    tracker.tracker.update_counts(mouse.lLastX);
+                    tracker.tracker.record_packet(mouse.lLastX, mouse.lLastY);
                 }
                 Err(e) => eprintln!("Failed to get raw input data: {}", e),
             }
@@ -108,6 +134,25 @@ impl MouseTracker {
     }
 
 
+    pub fn get_device_name(header: &RAWINPUTHEADER) -> Option<String> {
+        let mut size: UINT = 0;
+
+        unsafe {
+            if GetRawInputDeviceInfoW(header.hDevice, RIDI_DEVICENAME, null_mut(), &mut size) != 0 || size == 0 {
+                return None;
+            }
+
+            let mut name = vec![0u16; size as usize];
+            let written = GetRawInputDeviceInfoW(header.hDevice, RIDI_DEVICENAME, name.as_mut_ptr() as _, &mut size);
+            if written == UINT::MAX || written == 0 {
+                return None;
+            }
+
+            let len = name.iter().position(|&c| c == 0).unwrap_or(name.len());
+            Some(String::from_utf16_lossy(&name[..len]))
+        }
+    }
+
     pub fn get_raw_input_data(&self, h_raw_input: NonNull<std::ffi::c_void>) -> Result<RAWINPUT, String> {
         let mut size: UINT = 0;
         let header_size = std::mem::size_of::<RAWINPUTHEADER>() as UINT;
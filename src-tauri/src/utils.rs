@@ -1,18 +1,44 @@
-use crate::calculations::{calculate_counts, calculate_scoped_counts, calculate_yaw, estimate_fov};
-use crate::models::{AppSettings, CmUpdatePayload, FovUpdatePayload, GameYaw, UserSettings, YawStuff};
+use aimcalibrate_core::calculations::{calculate_counts, calculate_measured_cm, calculate_measured_fov, calculate_scoped_counts};
+use aimcalibrate_core::history::new_record;
+use aimcalibrate_core::input_analysis::{analyze_acceleration, analyze_polling};
+use aimcalibrate_core::measurement::{add_dpi_sample, drop_dpi_sample, finish_yaw_measurement, lower_yaw_estimate, raise_yaw_estimate};
+use aimcalibrate_core::models::{AppSettings, CalibrationWizard, CmUpdatePayload, DpiStuff, FovUpdatePayload, GameList, GameYaw, MeasurementKind, MeasurementRecord, SensFinder, StorageIssue, UserSettings, YawStuff};
+use aimcalibrate_core::sens_finder::{choose_candidate, undo_choice};
+use aimcalibrate_core::storage::{self, StorageContext};
+use aimcalibrate_core::wizard::{measure_wizard_fov, set_wizard_yaw, wizard_page};
 #[cfg(target_os = "windows")]
 use crate::mouse_tracker::{AppState, APP_STATE};
 #[cfg(not(target_os = "windows"))]
 use crate::mouse_tracker_mock::{AppState, APP_STATE};
+use crate::api_server;
 use enigo::{Enigo, Mouse, Settings};
+use once_cell::sync::{Lazy, OnceCell};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
+use serde::Serialize;
 use tauri::{AppHandle, GlobalShortcutManager, Manager, State};
 #[cfg(target_os = "windows")]
 use winapi::shared::windef::HWND;
 
+// Counts injected per run of the acceleration test, and the number of steps they are split into.
+// move_mouse_by waits 10ms between steps, so fewer steps means a faster movement. The counts are
+// kept low enough that the cursor doesn't reach the edge of the screen even with acceleration on.
+const ACCELERATION_TEST_COUNTS: i32 = 400;
+const ACCELERATION_TEST_STEPS: [i32; 6] = [40, 20, 8, 4, 2, 1];
+
+static ACCELERATION_TEST_RUNNING: AtomicBool = AtomicBool::new(false);
+
+// How often Games.json is checked for changes made outside the app
+const GAMES_WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+// Corrupt files found since the start, kept until the user dismisses them
+static STORAGE_ISSUES: Lazy<Mutex<Vec<StorageIssue>>> = Lazy::new(|| Mutex::new(Vec::new()));
+// Set once the window exists, issues found before that are picked up with get_storage_issues
+static STORAGE_ISSUE_HANDLE: OnceCell<AppHandle> = OnceCell::new();
+
 pub fn move_mouse_by(mut x: i32, steps: i32, right: bool) {
     let settings = Settings {
         windows_subject_to_mouse_speed_and_acceleration_level: true,
@@ -50,13 +76,20 @@ pub fn setup_global_shortcuts(handle: AppHandle) {
         let app_handle = app_handle.clone();
         global_shortcut_manager
             .register(hotkey, move || {
-                crate::utils::handle_hotkey(index, &app_handle);
+                crate::utils::handle_hotkey(index, None, &app_handle);
             })
             .unwrap();
     }
 }
 
-pub fn handle_hotkey(index: usize, app_handle: &AppHandle) {
+// Sends an event to the app window and to clients of the control API
+pub fn emit_update<S: Serialize + Clone>(app_handle: &AppHandle, event: &str, payload: S) {
+    api_server::broadcast(event, &payload);
+    app_handle.emit_all(event, payload).unwrap();
+}
+
+// `page_override` runs the actions of another page without switching the app to it
+pub fn handle_hotkey(index: usize, page_override: Option<&str>, app_handle: &AppHandle) {
     let app_state = APP_STATE.lock().unwrap().as_ref().unwrap().clone();
     let mut app_state = app_state.lock().unwrap();
     let params_state: State<Arc<Mutex<UserSettings>>> = app_handle.state();
@@ -65,13 +98,33 @@ pub fn handle_hotkey(index: usize, app_handle: &AppHandle) {
     let settings_params = settings_state.lock().unwrap();
     let yaw_state: State<Arc<Mutex<YawStuff>>> = app_handle.state();
     let mut yaw_params = yaw_state.lock().unwrap();
+    let dpi_state: State<Arc<Mutex<DpiStuff>>> = app_handle.state();
+    let mut dpi_params = dpi_state.lock().unwrap();
+    let finder_state: State<Arc<Mutex<SensFinder>>> = app_handle.state();
+    let mut finder = finder_state.lock().unwrap();
+    let wizard_state: State<Arc<Mutex<CalibrationWizard>>> = app_handle.state();
+    let mut wizard = wizard_state.lock().unwrap();
+
+    // On the wizard page the hotkeys run the actions of the page that measures the current step
+    let current_page = page_override.unwrap_or(&app_state.current_page).to_string();
+    let in_wizard = current_page == "calibration_wizard";
+    let page = if in_wizard {
+        wizard_page(&wizard).unwrap_or_default().to_string()
+    } else {
+        current_page
+    };
+    // Measurements are recorded for the game picked on the page, or the one the wizard sets up
+    let mut profile = params.clone();
+    if in_wizard {
+        profile.game = Some(wizard.game.clone());
+    }
 
     match index {
         0 => {
             // Hotkey 1 action
-            match app_state.current_page.as_str() {
+            match page.as_str() {
                 "main_sensitivity" => {
-                    let counts = calculate_counts(params.cm360, params.dpi);
+                    let counts = calculate_counts(params.cm360, params.dpi, settings_params.dpi_correction);
                     move_mouse_by(
                         counts,
                         (50.0 / settings_params.turn_speed) as i32,
@@ -82,6 +135,7 @@ pub fn handle_hotkey(index: usize, app_handle: &AppHandle) {
                     let counts = calculate_scoped_counts(
                         params.cm360,
                         params.dpi,
+                        settings_params.dpi_correction,
                         params.normal_fov,
                         params.scoped_fov,
                     );
@@ -95,18 +149,38 @@ pub fn handle_hotkey(index: usize, app_handle: &AppHandle) {
                     if app_state.tracker.tracking {
                         app_state.tracker.stop_tracking().unwrap();
 
-                        let inches_per_360 = params.cm360 / 2.54;
-                        let counts = inches_per_360 * params.dpi as f64;
-
-                        let fov = estimate_fov(
-                            params.game_sens,
-                            calculate_yaw(counts as i32, params.game_sens),
-                            app_state.tracker.count.abs(),
+                        let wizard_fov = if in_wizard {
+                            measure_wizard_fov(&mut wizard, app_state.tracker.count)
+                        } else {
+                            None
+                        };
+                        let fov = wizard_fov.unwrap_or_else(|| {
+                            calculate_measured_fov(
+                                params.cm360,
+                                params.dpi,
+                                settings_params.dpi_correction,
+                                params.game_sens,
+                                app_state.tracker.count,
+                            )
+                        });
+
+                        record_measurement(
+                            app_handle,
+                            new_record(
+                                MeasurementKind::Fov,
+                                "tracked_turn",
+                                app_state.tracker.count,
+                                fov,
+                                app_state.tracker.device.clone(),
+                                &profile,
+                                settings_params.dpi_correction,
+                            ),
                         );
 
-                        app_handle
-                            .emit_all("fov_update", FovUpdatePayload { fov16: fov })
-                            .unwrap();
+                        emit_update(app_handle, "fov_update", FovUpdatePayload { fov16: fov });
+                        if wizard_fov.is_some() {
+                            emit_update(app_handle, "wizard_update", wizard.clone());
+                        }
                     } else {
                         start_tracking(app_handle, &mut app_state);
                     }
@@ -115,19 +189,74 @@ pub fn handle_hotkey(index: usize, app_handle: &AppHandle) {
                     if app_state.tracker.tracking {
                         app_state.tracker.stop_tracking().unwrap();
 
-                        yaw_params.counts = app_state.tracker.count.abs();
-                        yaw_params.inc = 360.0 / yaw_params.counts as f64;
-                        yaw_params.yaw = yaw_params.inc / yaw_params.sens;
-                        yaw_params.lower_limit = yaw_params.yaw * 0.9;
-                        yaw_params.upper_limit = yaw_params.yaw * 1.1;
+                        finish_yaw_measurement(&mut yaw_params, app_state.tracker.count);
+                        record_measurement(
+                            app_handle,
+                            new_record(
+                                MeasurementKind::Yaw,
+                                "tracked_turn",
+                                yaw_params.counts,
+                                yaw_params.yaw,
+                                app_state.tracker.device.clone(),
+                                &profile,
+                                settings_params.dpi_correction,
+                            ),
+                        );
+
+                        emit_update(app_handle, "yaw_update", yaw_params.clone());
+                        if in_wizard {
+                            set_wizard_yaw(&mut wizard, &yaw_params);
+                            emit_update(app_handle, "wizard_update", wizard.clone());
+                        }
+                    } else {
+                        start_tracking(app_handle, &mut app_state);
+                    }
+                }
+                "measure_dpi" => {
+                    if app_state.tracker.tracking {
+                        app_state.tracker.stop_tracking().unwrap();
+
+                        add_dpi_sample(&mut dpi_params, app_state.tracker.count);
 
-                        app_handle
-                            .emit_all("yaw_update", yaw_params.clone())
-                            .unwrap();
+                        emit_update(app_handle, "dpi_update", dpi_params.clone());
                     } else {
                         start_tracking(app_handle, &mut app_state);
                     }
                 }
+                "polling_rate" => {
+                    if app_state.tracker.tracking {
+                        app_state.tracker.stop_tracking().unwrap();
+
+                        let report = analyze_polling(&app_state.tracker.packets);
+
+                        emit_update(app_handle, "polling_update", report);
+                    } else {
+                        start_tracking(app_handle, &mut app_state);
+                    }
+                }
+                "sens_finder" => {
+                    // Turns 360 with the candidate being tried, to check the game sensitivity is set right
+                    let counts = calculate_counts(
+                        finder.candidates[finder.active],
+                        finder.dpi,
+                        settings_params.dpi_correction,
+                    );
+                    move_mouse_by(
+                        counts,
+                        (50.0 / settings_params.turn_speed) as i32,
+                        true,
+                    );
+                }
+                "acceleration_test" => {
+                    // The test moves the mouse for a while, so it runs on its own thread
+                    if !app_state.tracker.tracking && !ACCELERATION_TEST_RUNNING.swap(true, Ordering::SeqCst) {
+                        let app_handle = app_handle.clone();
+                        std::thread::spawn(move || {
+                            run_acceleration_test(&app_handle);
+                            ACCELERATION_TEST_RUNNING.store(false, Ordering::SeqCst);
+                        });
+                    }
+                }
                 _ => {
                     println!("Hotkey pressed on unknown page");
                 }
@@ -135,16 +264,31 @@ pub fn handle_hotkey(index: usize, app_handle: &AppHandle) {
         }
         1 => {
             // Hotkey 2 action
-            match app_state.current_page.as_str() {
+            match page.as_str() {
                 "main_sensitivity" => {
                     if app_state.tracker.tracking {
                         app_state.tracker.stop_tracking().unwrap();
 
-                        let cm_per360 = 2.54 * app_state.tracker.count.abs() as f64 / params.dpi as f64;
+                        let cm_per360 = calculate_measured_cm(
+                            app_state.tracker.count,
+                            params.dpi,
+                            settings_params.dpi_correction,
+                        );
+
+                        record_measurement(
+                            app_handle,
+                            new_record(
+                                MeasurementKind::Cm360,
+                                "tracked_turn",
+                                app_state.tracker.count,
+                                cm_per360,
+                                app_state.tracker.device.clone(),
+                                &profile,
+                                settings_params.dpi_correction,
+                            ),
+                        );
 
-                        app_handle
-                            .emit_all("cm_update", CmUpdatePayload { cm_per360 })
-                            .unwrap();
+                        emit_update(app_handle, "cm_update", CmUpdatePayload { cm_per360 });
                     } else {
                         start_tracking(app_handle, &mut app_state);
                     }
@@ -156,6 +300,17 @@ pub fn handle_hotkey(index: usize, app_handle: &AppHandle) {
                         true,
                     );
                 }
+                "measure_dpi" => {
+                    drop_dpi_sample(&mut dpi_params);
+
+                    emit_update(app_handle, "dpi_update", dpi_params.clone());
+                }
+                "sens_finder" => {
+                    // Switch to the other candidate
+                    finder.active = 1 - finder.active;
+
+                    emit_update(app_handle, "sens_finder_update", finder.clone());
+                }
                 _ => {
                     println!("Hotkey pressed on unknown page");
                 }
@@ -163,40 +318,48 @@ pub fn handle_hotkey(index: usize, app_handle: &AppHandle) {
         }
         2 => {
             // Hotkey 3 action
-            if app_state.current_page == "measure_yaw" {
+            if page == "measure_yaw" {
                 move_mouse_by(
                     yaw_params.counts,
                     (50.0 / settings_params.turn_speed) as i32,
                     false,
                 );
 
-                yaw_params.upper_limit = yaw_params.yaw;
-                yaw_params.yaw = (yaw_params.upper_limit + yaw_params.lower_limit) / 2.0;
-                yaw_params.inc = yaw_params.sens * yaw_params.yaw;
-                yaw_params.counts = (360.0 / yaw_params.inc).round() as i32;
+                lower_yaw_estimate(&mut yaw_params);
 
-                app_handle
-                    .emit_all("yaw_update", yaw_params.clone())
-                    .unwrap();
+                emit_update(app_handle, "yaw_update", yaw_params.clone());
+                if in_wizard {
+                    set_wizard_yaw(&mut wizard, &yaw_params);
+                    emit_update(app_handle, "wizard_update", wizard.clone());
+                }
+            } else if page == "sens_finder" {
+                // Keep the candidate being tried
+                let active = finder.active;
+                choose_candidate(&mut finder, active, settings_params.dpi_correction);
+
+                emit_update(app_handle, "sens_finder_update", finder.clone());
             }
         }
         3 => {
             // Hotkey 4 action
-            if app_state.current_page == "measure_yaw" {
+            if page == "measure_yaw" {
                 move_mouse_by(
                     yaw_params.counts,
                     (50.0 / settings_params.turn_speed) as i32,
                     false,
                 );
 
-                yaw_params.lower_limit = yaw_params.yaw;
-                yaw_params.yaw = (yaw_params.upper_limit + yaw_params.lower_limit) / 2.0;
-                yaw_params.inc = yaw_params.sens * yaw_params.yaw;
-                yaw_params.counts = (360.0 / yaw_params.inc).round() as i32;
+                raise_yaw_estimate(&mut yaw_params);
+
+                emit_update(app_handle, "yaw_update", yaw_params.clone());
+                if in_wizard {
+                    set_wizard_yaw(&mut wizard, &yaw_params);
+                    emit_update(app_handle, "wizard_update", wizard.clone());
+                }
+            } else if page == "sens_finder" {
+                undo_choice(&mut finder, settings_params.dpi_correction);
 
-                app_handle
-                    .emit_all("yaw_update", yaw_params.clone())
-                    .unwrap();
+                emit_update(app_handle, "sens_finder_update", finder.clone());
             }
         }
         _ => {
@@ -205,6 +368,56 @@ pub fn handle_hotkey(index: usize, app_handle: &AppHandle) {
     }
 }
 
+// Appends a completed measurement to the history. A failed write only costs the entry, the
+// measurement itself still goes through.
+pub fn record_measurement(app_handle: &AppHandle, record: MeasurementRecord) {
+    let storage: State<StorageContext> = app_handle.state();
+    match storage::append_history(&storage.history_path(), &record) {
+        Ok(()) => emit_update(app_handle, "measurement_recorded", record),
+        Err(e) => println!("Failed to write measurement history: {}", e),
+    }
+}
+
+// Raw input sees the injected counts before the pointer ballistics, so the test compares them with
+// how far the cursor really moved
+pub fn run_acceleration_test(app_handle: &AppHandle) {
+    let runs = match acceleration_runs() {
+        Ok(runs) => runs,
+        Err(e) => {
+            println!("Acceleration test failed: {}", e);
+            Vec::new()
+        }
+    };
+
+    emit_update(app_handle, "acceleration_update", analyze_acceleration(&runs));
+}
+
+// (speed in counts/s, injected counts, pixels the cursor moved) per run
+fn acceleration_runs() -> Result<Vec<(f64, i32, i32)>, String> {
+    let mut enigo = Enigo::new(&Settings::default()).map_err(|e| e.to_string())?;
+    let (width, height) = enigo.main_display().map_err(|e| e.to_string())?;
+    let mut runs = Vec::new();
+
+    for &steps in ACCELERATION_TEST_STEPS.iter() {
+        // Each run starts near the left edge so the cursor has most of the screen to move
+        enigo
+            .move_mouse(width / 8, height / 2, enigo::Coordinate::Abs)
+            .map_err(|e| e.to_string())?;
+        std::thread::sleep(Duration::from_millis(50));
+        let (start, _) = enigo.location().map_err(|e| e.to_string())?;
+
+        move_mouse_by(ACCELERATION_TEST_COUNTS, steps, true);
+        // Give the last movement time to reach the cursor before reading its position
+        std::thread::sleep(Duration::from_millis(100));
+        let (end, _) = enigo.location().map_err(|e| e.to_string())?;
+
+        let speed = (ACCELERATION_TEST_COUNTS / steps) as f64 * 100.0;
+        runs.push((speed, ACCELERATION_TEST_COUNTS, end - start));
+    }
+
+    Ok(runs)
+}
+
 #[cfg(target_os = "windows")]
 pub fn start_tracking(app_handle: &AppHandle, app_state: &mut AppState) {
     let window = app_handle.get_window("main").unwrap();
@@ -213,92 +426,145 @@ pub fn start_tracking(app_handle: &AppHandle, app_state: &mut AppState) {
         Err(_) => panic!("Failed to get window handle"),
     };
     app_state.tracker.start_tracking(hwnd).unwrap();
+    app_state.tracker.page = app_state.current_page.clone();
 }
 
 #[cfg(not(target_os = "windows"))]
 pub fn start_tracking(_app_handle: &AppHandle, app_state: &mut AppState) {
     app_state.tracker.start_tracking().unwrap();
+    app_state.tracker.page = app_state.current_page.clone();
 }
 
 pub fn save_app_settings(
+    storage: &StorageContext,
     state: State<Arc<Mutex<AppSettings>>>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let settings = state.lock().unwrap();
-    let path = get_settings_path();
-    let data = serde_json::to_string(&*settings)?;
-    fs::write(path, data)?;
-    Ok(())
+    storage::save_app_settings(&storage.settings_path(), &settings)
 }
 
-pub fn load_app_settings() -> Result<AppSettings, Box<dyn std::error::Error>> {
-    let path = get_settings_path();
-    if path.exists() {
-        let data = fs::read_to_string(&path)?;
-        // Use serde_json's `from_str` with `default` to fill missing fields
-        let mut settings: AppSettings = serde_json::from_str(&data).unwrap_or_else(|_| AppSettings::default());
-
-        // Ensure that the `hotkeys` vector is populated
-        if settings.hotkeys.is_empty() {
-            settings.hotkeys = vec![
-                "F1".to_string(),
-                "F2".to_string(),
-                "F3".to_string(),
-                "F4".to_string(),
-            ];
-        }
+pub fn load_app_settings(storage: &StorageContext) -> Result<AppSettings, Box<dyn std::error::Error>> {
+    let (settings, issue) = storage::load_app_settings(&storage.settings_path())?;
+    report_storage_issue(issue);
+    Ok(settings)
+}
 
-        // Save the updated settings back to the file
-        let updated_data = serde_json::to_string(&settings)?;
-        fs::write(&path, updated_data)?;
+pub fn load_games(storage: &StorageContext) -> Result<Vec<GameYaw>, Box<dyn std::error::Error>> {
+    let (games, issue) = storage::load_games(&storage.games_path())?;
+    report_storage_issue(issue);
+    Ok(games)
+}
 
-        Ok(settings)
-    } else {
-        let default_settings = AppSettings::default();
-        let data = serde_json::to_string(&default_settings)?;
-        fs::write(&path, data)?;
-        Ok(default_settings)
-    }
+// Watches Games.json for edits made outside the app, like by hand or with the command line. A
+// file that parses replaces the list in memory. Otherwise the last good list is kept and sent
+// along with the error, and the file is left alone for the user to fix. A file that disappears
+// is an error too, so deleting or moving it never empties the list.
+pub fn watch_games_file(app_handle: AppHandle) {
+    let path = app_handle.state::<StorageContext>().games_path();
+
+    std::thread::spawn(move || {
+        let mut last_stamp = file_stamp(&path);
+        loop {
+            std::thread::sleep(GAMES_WATCH_INTERVAL);
+
+            let stamp = file_stamp(&path);
+            if stamp == last_stamp {
+                continue;
+            }
+            last_stamp = stamp;
+
+            let list = {
+                let list_state: State<Arc<Mutex<GameList>>> = app_handle.state();
+                let mut list = list_state.lock().unwrap();
+                let games = match fs::read_to_string(&path) {
+                    Ok(data) => serde_json::from_str::<Vec<GameYaw>>(&data).map_err(|e| e.to_string()),
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                        Err(format!("{} was removed", path.display()))
+                    }
+                    Err(e) => Err(e.to_string()),
+                };
+                match games {
+                    Ok(games) => {
+                        list.games = games;
+                        list.error = None;
+                    }
+                    Err(e) => list.error = Some(e),
+                }
+                list.clone()
+            };
+            emit_update(&app_handle, "games_changed", list);
+        }
+    });
 }
 
-pub fn get_settings_path() -> PathBuf {
-    let config_dir = tauri::api::path::app_config_dir(&tauri::Config::default())
-        .expect("Failed to get config directory")
-        .join("AimCalibrate");
+fn file_stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
 
-    if !config_dir.exists() {
-        fs::create_dir_all(&config_dir).expect("Failed to create config directory");
-    }
+pub fn setup_storage_issue_events(handle: AppHandle) {
+    let _ = STORAGE_ISSUE_HANDLE.set(handle);
+}
 
-    config_dir.join("settings.json")
+pub fn storage_issues() -> Vec<StorageIssue> {
+    STORAGE_ISSUES.lock().unwrap().clone()
 }
 
-pub fn get_yaw_file_path() -> PathBuf {
-    let config_dir = tauri::api::path::app_config_dir(&tauri::Config::default())
-        .expect("Failed to get config directory")
-        .join("AimCalibrate");
+pub fn clear_storage_issues() {
+    STORAGE_ISSUES.lock().unwrap().clear();
+}
+
+fn report_storage_issue(issue: Option<StorageIssue>) {
+    let Some(issue) = issue else {
+        return;
+    };
 
-    if !config_dir.exists() {
-        fs::create_dir_all(&config_dir).expect("Failed to create config directory");
+    eprintln!(
+        "{} could not be read ({}), it was moved to {}",
+        issue.path, issue.error, issue.quarantined
+    );
+    if let Some(backup) = &issue.restored_from {
+        eprintln!("Restored {} from {}", issue.path, backup);
     }
 
-    config_dir.join("Games.json")
+    STORAGE_ISSUES.lock().unwrap().push(issue.clone());
+    if let Some(app_handle) = STORAGE_ISSUE_HANDLE.get() {
+        emit_update(app_handle, "storage_issue", issue);
+    }
 }
 
-pub fn load_yaw_data(path: &PathBuf) -> Result<Vec<GameYaw>, Box<dyn std::error::Error>> {
-    if path.exists() {
-        let data = fs::read_to_string(path)?;
-        let game_yaws: Vec<GameYaw> = serde_json::from_str(&data)?;
-        Ok(game_yaws)
-    } else {
-        Ok(vec![])
+// Takes --config-dir and --portable out of the arguments so they work for the app and for every
+// command line command, then resolves and creates the config folder
+pub fn resolve_storage(args: &mut Vec<String>) -> Result<StorageContext, String> {
+    let mut flag_dir = None;
+    let mut portable = false;
+
+    let mut index = 1;
+    while index < args.len() {
+        match args[index].as_str() {
+            "--portable" => {
+                args.remove(index);
+                portable = true;
+            }
+            "--config-dir" => {
+                args.remove(index);
+                if index >= args.len() {
+                    return Err("Missing value for --config-dir".to_string());
+                }
+                flag_dir = Some(PathBuf::from(args.remove(index)));
+            }
+            _ => index += 1,
+        }
     }
-}
 
-pub fn save_yaw_data(
-    path: &PathBuf,
-    game_yaws: &Vec<GameYaw>,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let data = serde_json::to_string(game_yaws)?;
-    fs::write(path, data)?;
-    Ok(())
-}
\ No newline at end of file
+    let env_dir = std::env::var_os(storage::CONFIG_DIR_ENV)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from);
+    let exe_dir = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(PathBuf::from));
+    let platform_dir = tauri::api::path::app_config_dir(&tauri::Config::default());
+
+    let (config_dir, portable) = storage::resolve_config_dir(flag_dir, env_dir, portable, exe_dir.as_deref(), platform_dir)?;
+    Ok(StorageContext::new(config_dir, portable))
+}
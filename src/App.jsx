@@ -1,6 +1,7 @@
-import React from 'react';
+import React, {useEffect, useState} from 'react';
 import {BrowserRouter as Router, NavLink, Route, Routes} from 'react-router-dom';
 import {invoke} from '@tauri-apps/api/tauri';
+import {listen} from '@tauri-apps/api/event';
 import './App.css';
 import MainSensitivity from './pages/MainSensitivity';
 import ScopedSensitivity from './pages/ScopedSensitivity';
@@ -9,9 +10,45 @@ import Settings from "./pages/Settings.jsx";
 import MeasureYaw from "./pages/MeasureYaw.jsx";
 import logo from '/acLogo.png';
 import Converter from "./pages/Converter.jsx"; // Adjust the path based on your project structure
+import MeasureDpi from "./pages/MeasureDpi.jsx";
+import PollingRate from "./pages/PollingRate.jsx";
+import AccelerationTest from "./pages/AccelerationTest.jsx";
+import DpiOptimizer from "./pages/DpiOptimizer.jsx";
+import ScopeTable from "./pages/ScopeTable.jsx";
+import Gamepad from "./pages/Gamepad.jsx";
+import AccelCurve from "./pages/AccelCurve.jsx";
+import SensFinder from "./pages/SensFinder.jsx";
+import History from "./pages/History.jsx";
+import CalibrationWizard from "./pages/CalibrationWizard.jsx";
 
 function App() {
+    const [storageIssues, setStorageIssues] = useState([]);
 
+    useEffect(() => {
+        let unlisten;
+
+        const fetchStorageIssues = async () => {
+            try {
+                setStorageIssues(await invoke('get_storage_issues'));
+                unlisten = await listen('storage_issue', (event) => {
+                    setStorageIssues((issues) => [...issues, event.payload]);
+                });
+            } catch (error) {
+                console.error('Failed to fetch storage issues:', error);
+            }
+        };
+
+        fetchStorageIssues();
+
+        return () => {
+            if (unlisten) unlisten();
+        };
+    }, []);
+
+    const dismissStorageIssues = () => {
+        invoke('dismiss_storage_issues').catch((err) => console.error(err));
+        setStorageIssues([]);
+    };
 
     const setPage = (page) => {
         invoke('set_current_page', {page}).catch((err) => console.error(err));
@@ -28,10 +65,17 @@ function App() {
                         <li>
                             <NavLink exact to="/" onClick={() => setPage("main_sensitivity")}>Main Sensitivity</NavLink>
                         </li>
+                        <li>
+                            <NavLink to="/calibration-wizard" onClick={() => setPage("calibration_wizard")}>New Game
+                                Wizard</NavLink>
+                        </li>
                         <li>
                             <NavLink to="/scoped-sensitivity" onClick={() => setPage("scoped_sensitivity")}>Scoped
                                 Sensitivity</NavLink>
                         </li>
+                        <li>
+                            <NavLink to="/scope-table" onClick={() => setPage("scope_table")}>Scope Table</NavLink>
+                        </li>
                         <li>
                             <NavLink to="/measure-fov" onClick={() => setPage("measure_fov")}>Measure FOV</NavLink>
                         </li>
@@ -41,12 +85,50 @@ function App() {
                         <li>
                             <NavLink to="/converter" onClick={() => setPage("converter")}>Converter</NavLink>
                         </li>
+                        <li>
+                            <NavLink to="/dpi-optimizer" onClick={() => setPage("dpi_optimizer")}>DPI Optimizer</NavLink>
+                        </li>
+                        <li>
+                            <NavLink to="/gamepad" onClick={() => setPage("gamepad")}>Gamepad</NavLink>
+                        </li>
+                        <li>
+                            <NavLink to="/accel-curve" onClick={() => setPage("accel_curve")}>Accel Curve</NavLink>
+                        </li>
+                        <li>
+                            <NavLink to="/sens-finder" onClick={() => setPage("sens_finder")}>Sens Finder</NavLink>
+                        </li>
+                        <li>
+                            <NavLink to="/measure-dpi" onClick={() => setPage("measure_dpi")}>Measure DPI</NavLink>
+                        </li>
+                        <li>
+                            <NavLink to="/polling-rate" onClick={() => setPage("polling_rate")}>Polling Rate</NavLink>
+                        </li>
+                        <li>
+                            <NavLink to="/acceleration-test" onClick={() => setPage("acceleration_test")}>Acceleration
+                                Test</NavLink>
+                        </li>
+                        <li>
+                            <NavLink to="/history" onClick={() => setPage("history")}>History</NavLink>
+                        </li>
                     </ul>
                     <div className="sidebar-settings">
                         <NavLink to="/settings" onClick={() => setPage("settings")}>Settings</NavLink>
                     </div>
                 </div>
                 <div className="main-content">
+                    {storageIssues.length > 0 && (
+                        <div className="storage-issues">
+                            {storageIssues.map((issue) => (
+                                <div key={issue.quarantined}>
+                                    {issue.path} was damaged and moved to {issue.quarantined}.
+                                    {issue.restored_from
+                                        ? ` Restored the backup ${issue.restored_from}.`
+                                        : ' No good backup was found, defaults are used.'}
+                                </div>
+                            ))}
+                            <button className="keybind-button" onClick={dismissStorageIssues}>Dismiss</button>
+                        </div>
+                    )}
                     <Routes>
                         <Route path="/" element={<MainSensitivity/>}/>
                         <Route path="/scoped-sensitivity" element={<ScopedSensitivity/>}/>
@@ -54,6 +136,16 @@ function App() {
                         <Route path="/measure-yaw" element={<MeasureYaw/>}/>
                         <Route path="/settings" element={<Settings/>}/>
                         <Route path="/converter" element={<Converter/>}/>
+                        <Route path="/measure-dpi" element={<MeasureDpi/>}/>
+                        <Route path="/polling-rate" element={<PollingRate/>}/>
+                        <Route path="/acceleration-test" element={<AccelerationTest/>}/>
+                        <Route path="/dpi-optimizer" element={<DpiOptimizer/>}/>
+                        <Route path="/scope-table" element={<ScopeTable/>}/>
+                        <Route path="/gamepad" element={<Gamepad/>}/>
+                        <Route path="/accel-curve" element={<AccelCurve/>}/>
+                        <Route path="/sens-finder" element={<SensFinder/>}/>
+                        <Route path="/history" element={<History/>}/>
+                        <Route path="/calibration-wizard" element={<CalibrationWizard/>}/>
                     </Routes>
                 </div>
             </div>
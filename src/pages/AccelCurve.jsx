@@ -0,0 +1,156 @@
+import React, {useEffect, useState} from 'react';
+import {invoke} from '@tauri-apps/api/tauri';
+import {listen} from '@tauri-apps/api/event';
+import debounce from 'lodash/debounce';
+import {Tooltip as ReactTooltip} from 'react-tooltip';
+import {FontAwesomeIcon} from '@fortawesome/react-fontawesome';
+import {faQuestionCircle} from '@fortawesome/free-solid-svg-icons';
+
+// Parameters each curve type takes, in the order they are shown
+const CURVES = {
+    linear: ['accel', 'offset', 'cap'],
+    classic: ['accel', 'exponent', 'offset', 'cap'],
+    power: ['scale', 'exponent', 'output_offset', 'cap'],
+    natural: ['decay', 'limit', 'offset'],
+};
+
+function AccelCurve() {
+    const [games, setGames] = useState([]);
+    const [sourceGame, setSourceGame] = useState('');
+    const [destGame, setDestGame] = useState('');
+    const [sens, setSens] = useState(1);
+    const [dpi, setDpi] = useState(800);
+    const [newDpi, setNewDpi] = useState(800);
+    const [curveType, setCurveType] = useState('linear');
+    const [params, setParams] = useState({accel: 0.1, exponent: 2, scale: 1, limit: 1.5, decay: 0.1});
+    const [conversion, setConversion] = useState(null);
+
+    useEffect(() => {
+        invoke('get_games')
+            .then(setGames)
+            .catch((error) => console.error('Failed to fetch games: ', error));
+    }, []);
+
+    useEffect(() => {
+        const unlisten = listen('games_changed', (event) => {
+            setGames(event.payload.games);
+        });
+
+        return () => {
+            unlisten.then((stop) => stop());
+        };
+    }, []);
+
+    const buildCurve = () => {
+        const curve = {type: curveType};
+        CURVES[curveType].forEach((param) => {
+            const value = parseFloat(params[param]);
+            if (!isNaN(value)) curve[param] = value;
+        });
+        return curve;
+    };
+
+    const convert = debounce(async () => {
+        try {
+            const source = games.find((game) => game.name === sourceGame);
+            const dest = games.find((game) => game.name === destGame);
+            if (source && dest) {
+                const response = await invoke('convert_accel', {
+                    curve: buildCurve(),
+                    sens: parseFloat(sens),
+                    dpi: parseInt(dpi),
+                    yaw1: source.yaw,
+                    newDpi: parseInt(newDpi),
+                    yaw2: dest.yaw,
+                });
+                setConversion(response);
+            }
+        } catch (error) {
+            console.error('Failed to convert acceleration curve', error);
+        }
+    }, 500);
+
+    useEffect(() => {
+        convert();
+    }, [sourceGame, destGame, sens, dpi, newDpi, curveType, params]);
+
+    const gameSelect = (id, value, onChange) => (
+        <select id={id} value={value} onChange={(e) => onChange(e.target.value)}>
+            <option value="">Select a game</option>
+            {games.map((game) => (
+                <option key={game.name} value={game.name}>{game.name}</option>
+            ))}
+        </select>
+    );
+
+    return (
+        <div className="main-container">
+            <ReactTooltip id="info-tooltip" className="tooltip-box"/>
+            <div className="info-container">
+                <FontAwesomeIcon icon={faQuestionCircle}
+                                 data-tooltip-id="info-tooltip"
+                                 data-tooltip-content="This page converts an acceleration curve between games and DPI values.
+1. Select source and destination game and enter your sensitivity and DPI
+2. Select the curve type and enter its parameters
+3. The converted curve gives the same cm/360 at every hand speed"
+                                 data-tooltip-place="left" className="info-icon"/>
+            </div>
+            <div className="limits-group">
+                <div className="input-group">
+                    <label htmlFor="source-game">Source game:</label>
+                    {gameSelect('source-game', sourceGame, setSourceGame)}
+                </div>
+                <div className="input-group">
+                    <label htmlFor="dest-game">Destination game:</label>
+                    {gameSelect('dest-game', destGame, setDestGame)}
+                </div>
+            </div>
+            <div className="limits-group">
+                <div className="input-group">
+                    <label htmlFor="sens">Sensitivity:</label>
+                    <input type="number" id="sens" value={sens} onChange={(e) => setSens(e.target.value)}/>
+                </div>
+                <div className="input-group">
+                    <label htmlFor="dpi">DPI:</label>
+                    <input type="number" id="dpi" value={dpi} onChange={(e) => setDpi(e.target.value)}/>
+                </div>
+                <div className="input-group">
+                    <label htmlFor="new-dpi">New DPI:</label>
+                    <input type="number" id="new-dpi" value={newDpi} onChange={(e) => setNewDpi(e.target.value)}/>
+                </div>
+            </div>
+            <div className="input-group">
+                <label htmlFor="curve-type">Curve:</label>
+                <select id="curve-type" value={curveType} onChange={(e) => setCurveType(e.target.value)}>
+                    {Object.keys(CURVES).map((type) => (
+                        <option key={type} value={type}>{type}</option>
+                    ))}
+                </select>
+            </div>
+            <div className="limits-group">
+                {CURVES[curveType].map((param) => (
+                    <div className="input-group" key={param}>
+                        <label htmlFor={param}>{param}:</label>
+                        <input type="number" id={param} value={params[param] ?? ''}
+                               onChange={(e) => setParams({...params, [param]: e.target.value})}/>
+                    </div>
+                ))}
+            </div>
+            {conversion && (
+                <div className="current-keybind">
+                    <div>Sensitivity: {conversion.sens.toFixed(4)}</div>
+                    {CURVES[conversion.curve.type].map((param) => (
+                        conversion.curve[param] != null && (
+                            <div key={param}>{param}: {conversion.curve[param].toFixed(4)}</div>
+                        )
+                    ))}
+                    {conversion.samples.filter((_, index) => index % 7 === 0).map(([speed, cm]) => (
+                        <div key={speed}>{speed.toFixed(0)} cm/s: {cm.toFixed(2)} cm/360</div>
+                    ))}
+                </div>
+            )}
+        </div>
+    );
+}
+
+export default AccelCurve;
@@ -0,0 +1,52 @@
+import React, {useEffect, useState} from 'react';
+import {listen} from '@tauri-apps/api/event';
+import {Tooltip as ReactTooltip} from 'react-tooltip';
+import {FontAwesomeIcon} from '@fortawesome/react-fontawesome';
+import {faQuestionCircle} from '@fortawesome/free-solid-svg-icons';
+
+function AccelerationTest() {
+    const [report, setReport] = useState(null);
+
+    useEffect(() => {
+        let unlisten;
+
+        const startListener = async () => {
+            unlisten = await listen('acceleration_update', (event) => {
+                setReport(event.payload);
+            });
+        };
+
+        startListener();
+
+        return () => {
+            if (unlisten) unlisten();
+        };
+    }, []);
+
+    return (
+        <div className="main-container">
+            <ReactTooltip id="info-tooltip" className="tooltip-box"/>
+            <div className="info-container">
+                <FontAwesomeIcon icon={faQuestionCircle}
+                                 data-tooltip-id="info-tooltip"
+                                 data-tooltip-content="This page checks if mouse acceleration affects your input.
+1. Don't touch the mouse during the test
+2. Press hotkey 1. The same number of counts is sent at several speeds and the cursor's movement is measured
+3. If the cursor moves further at higher speeds, acceleration is active"
+                                 data-tooltip-place="left" className="info-icon"/>
+            </div>
+            {report && (
+                <div className="current-keybind">
+                    <div>{report.message}</div>
+                    {report.samples.map((sample) => (
+                        <div key={sample.speed}>
+                            {sample.speed.toFixed(0)} counts/s: {sample.received} px for {sample.expected} counts ({sample.ratio.toFixed(3)})
+                        </div>
+                    ))}
+                </div>
+            )}
+        </div>
+    );
+}
+
+export default AccelerationTest;
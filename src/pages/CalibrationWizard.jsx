@@ -0,0 +1,191 @@
+import React, {useEffect, useState} from 'react';
+import {invoke} from '@tauri-apps/api/tauri';
+import {listen} from '@tauri-apps/api/event';
+import {Tooltip as ReactTooltip} from 'react-tooltip';
+import {FontAwesomeIcon} from '@fortawesome/react-fontawesome';
+import {faQuestionCircle} from '@fortawesome/free-solid-svg-icons';
+import Button from '@mui/material/Button';
+
+const STEP_HINTS = {
+    measure_yaw: 'Press hotkey 1, turn 360 degrees in game and press hotkey 1 again. Hotkey 2 turns with the measured yaw, hotkey 3 and 4 correct it if the turn was too short or too far.',
+    measure_hipfire_fov: 'Line up something at the edge of your screen without scoping in. Press hotkey 1, move your crosshair to it and press hotkey 1 again.',
+    measure_scope_fov: 'Scope in and line up something at the edge of your screen. Scope out, press hotkey 1, move your crosshair to it and press hotkey 1 again.',
+};
+
+function CalibrationWizard() {
+    const [game, setGame] = useState('');
+    const [sens, setSens] = useState(1);
+    const [cm360, setCm360] = useState('');
+    const [dpi, setDpi] = useState('');
+    const [rawInput, setRawInput] = useState(true);
+    const [convention, setConvention] = useState('multiplier');
+    const [scopes, setScopes] = useState('');
+    const [wizard, setWizard] = useState(null);
+    const [error, setError] = useState('');
+
+    useEffect(() => {
+        let unlisten;
+
+        const fetchInitialValues = async () => {
+            try {
+                setWizard(await invoke('get_calibration_wizard'));
+                const profile = await invoke('get_initial_values');
+                if (profile.cm360 > 0) setCm360(profile.cm360);
+                if (profile.dpi > 0) setDpi(profile.dpi);
+                unlisten = await listen('wizard_update', (event) => {
+                    setWizard(event.payload);
+                });
+            } catch (error) {
+                console.error('Failed to fetch initial values:', error);
+            }
+        };
+
+        fetchInitialValues();
+
+        return () => {
+            if (unlisten) unlisten();
+        };
+    }, []);
+
+    const handleStart = async () => {
+        try {
+            const response = await invoke('start_calibration_wizard', {
+                plan: {
+                    game,
+                    sens: parseFloat(sens),
+                    cm_per360: parseFloat(cm360) || 0,
+                    dpi: parseInt(dpi) || 0,
+                    raw_input: rawInput,
+                    scope_convention: convention,
+                    scopes: scopes.split(',').map((scope) => scope.trim()).filter((scope) => scope),
+                },
+            });
+            setWizard(response);
+            setError('');
+        } catch (error) {
+            setError(`${error}`);
+        }
+    };
+
+    const handleNext = async () => {
+        try {
+            setWizard(await invoke('advance_calibration_wizard'));
+            setError('');
+        } catch (error) {
+            setError(`${error}`);
+        }
+    };
+
+    const handleCancel = async () => {
+        setWizard(await invoke('cancel_calibration_wizard'));
+        setError('');
+    };
+
+    const stepTitle = () => {
+        switch (wizard.step) {
+            case 'measure_yaw':
+                return 'Step 1: Measure the yaw';
+            case 'measure_hipfire_fov':
+                return 'Step 2: Measure the hipfire FOV';
+            default:
+                return `Step ${wizard.scope_index + 3}: Measure the FOV of ${wizard.scopes[wizard.scope_index].name}`;
+        }
+    };
+
+    const running = wizard && wizard.step !== 'idle' && wizard.step !== 'done';
+
+    return (
+        <div className="main-container">
+            <ReactTooltip id="info-tooltip" className="tooltip-box"/>
+            <div className="info-container">
+                <FontAwesomeIcon icon={faQuestionCircle}
+                                 data-tooltip-id="info-tooltip"
+                                 data-tooltip-content="This page sets up a new game in one go.
+1. Enter the name of the game, the sensitivity you have set in game, the cm/360 and DPI you want to play with and the names of its scopes, then press Start
+2. Follow the steps, the hotkeys measure the step that is shown. Press Next once a step is right
+3. Keep the in-game sensitivity the same until the end, all turns are made with it
+4. At the end the game is in your game list with its yaw and scope FOVs, together with the sensitivities for that cm/360 and DPI"
+                                 data-tooltip-place="left" className="info-icon"/>
+            </div>
+            {!running && (
+                <>
+                    <div className="input-group">
+                        <label htmlFor="game">Game:</label>
+                        <input type="text" id="game" value={game} onChange={(e) => setGame(e.target.value)}/>
+                    </div>
+                    <div className="limits-group">
+                        <div className="input-group">
+                            <label htmlFor="sens">Game Sens:</label>
+                            <input type="number" id="sens" value={sens} onChange={(e) => setSens(e.target.value)}/>
+                        </div>
+                        <div className="input-group">
+                            <label htmlFor="cm360">cm/360:</label>
+                            <input type="number" id="cm360" value={cm360} onChange={(e) => setCm360(e.target.value)}/>
+                        </div>
+                        <div className="input-group">
+                            <label htmlFor="dpi">DPI:</label>
+                            <input type="number" id="dpi" value={dpi} onChange={(e) => setDpi(e.target.value)}/>
+                        </div>
+                    </div>
+                    <div className="limits-group">
+                        <div className="input-group">
+                            <label htmlFor="convention">Scope sensitivity:</label>
+                            <select id="convention" value={convention} onChange={(e) => setConvention(e.target.value)}>
+                                <option value="multiplier">Multiplier</option>
+                                <option value="absolute">Absolute</option>
+                                <option value="relative_to_fov">Relative to FOV</option>
+                                <option value="coefficient">Coefficient</option>
+                            </select>
+                        </div>
+                    </div>
+                    <div className="input-group">
+                        <label htmlFor="scopes">Scopes:</label>
+                        <input type="text" id="scopes" value={scopes} placeholder="Red dot, 4x, 8x"
+                               onChange={(e) => setScopes(e.target.value)}/>
+                    </div>
+                    <label>
+                        <input
+                            type="checkbox"
+                            checked={rawInput}
+                            onChange={(e) => setRawInput(e.target.checked)}
+                        />
+                        Game uses raw input
+                    </label>
+                    <div className="button-container">
+                        <Button variant="outlined" onClick={handleStart} disabled={!game || !cm360 || !dpi}>Start</Button>
+                    </div>
+                </>
+            )}
+            <div>{error}</div>
+            {running && (
+                <div className="current-keybind">
+                    <div>{wizard.game}: {stepTitle()}</div>
+                    <div>{STEP_HINTS[wizard.step]}</div>
+                    <div>
+                        {wizard.pending !== null
+                            ? `Measured: ${wizard.pending.toFixed(wizard.step === 'measure_yaw' ? 6 : 2)}`
+                            : 'Not measured yet'}
+                    </div>
+                    <div className="button-container">
+                        <Button variant="outlined" onClick={handleNext} disabled={wizard.pending === null}>Next</Button>
+                        <Button variant="outlined" onClick={handleCancel}>Cancel</Button>
+                    </div>
+                </div>
+            )}
+            {wizard && wizard.step === 'done' && wizard.table && (
+                <div className="current-keybind">
+                    <div>{wizard.game} was added to the game list</div>
+                    <div>Yaw: {wizard.yaw.toFixed(6)}, hipfire FOV: {wizard.hipfire_fov.toFixed(2)}</div>
+                    <div>Hipfire sensitivity for {wizard.cm_per360} cm/360 at {wizard.dpi} DPI: {wizard.table.hipfire_sens.toFixed(4)}</div>
+                    {wizard.table.rows.map((row) => (
+                        <div key={row.name}>
+                            {row.name}: {row.sens.toFixed(4)} (FOV {row.fov.toFixed(1)}, {row.cm_per360.toFixed(2)} cm/360)
+                        </div>
+                    ))}
+                </div>
+            )}
+        </div>
+    );
+}
+
+export default CalibrationWizard;
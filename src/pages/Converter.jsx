@@ -1,5 +1,6 @@
 import React, {useEffect, useRef, useState} from 'react';
 import {invoke} from '@tauri-apps/api/tauri';
+import {listen} from '@tauri-apps/api/event';
 import debounce from 'lodash/debounce';
 import {Tooltip as ReactTooltip} from 'react-tooltip';
 import {FontAwesomeIcon} from '@fortawesome/react-fontawesome';
@@ -8,6 +9,28 @@ import Select from '@mui/material/Select';
 import MenuItem from '@mui/material/MenuItem';
 import InputLabel from '@mui/material/InputLabel';
 import FormControl from '@mui/material/FormControl';
+import Button from '@mui/material/Button';
+
+// Units that are not a game. DPI only matters for the ones that count mouse counts.
+const UNITS = [
+    {name: 'cm/360', unit: {type: 'cm360'}, usesDpi: false},
+    {name: 'inches/360', unit: {type: 'inches_per360'}, usesDpi: false},
+    {name: 'deg/count', unit: {type: 'deg_per_count'}, usesDpi: true},
+    {name: 'counts/360', unit: {type: 'counts_per360'}, usesDpi: true},
+    {name: 'deg/mm', unit: {type: 'deg_per_mm'}, usesDpi: false},
+];
+
+const gameUnit = (game) => ({
+    type: 'game',
+    yaw: game.yaw,
+    yaw_curve: game.yaw_curve,
+    raw_input: game.raw_input,
+    granularity: {
+        sens_step: game.sens_step,
+        sens_min: game.sens_min,
+        sens_max: game.sens_max,
+    },
+});
 
 function Converter() {
     const [games, setGames] = useState([]);
@@ -15,8 +38,12 @@ function Converter() {
     const [destGameIndex, setDestGameIndex] = useState(null);
     const [sourceSens, setSourceSens] = useState(0);
     const [newSens, setNewSens] = useState(0);
+    const [conversion, setConversion] = useState(null);
+    const [allGames, setAllGames] = useState([]);
     const [sourceDpi, setSourceDpi] = useState(0);
     const [destDpi, setDestDpi] = useState(0);
+    const [configMessage, setConfigMessage] = useState('');
+    const [gamesError, setGamesError] = useState('');
 
     const isInitialMount = useRef(true);
 
@@ -33,17 +60,33 @@ function Converter() {
 
         fetchInitialValues();
 
+        const showGames = (response) => {
+            const updatedGames = [
+                ...UNITS,
+                ...response.map((game) => ({...game, unit: gameUnit(game), usesDpi: true, isGame: true})),
+            ];
+            setGames(updatedGames);
+        };
+
         const fetchGames = async () => {
             try {
-                const response = await invoke('get_games');
-                const updatedGames = [{name: 'cm/360', yaw: 360.0}, ...response];
-                setGames(updatedGames);
+                showGames(await invoke('get_games'));
             } catch (error) {
                 console.error('Failed to fetch games: ', error);
             }
         };
 
         fetchGames();
+
+        // Games.json was changed outside the app. On an error the last good list is kept.
+        const unlisten = listen('games_changed', (event) => {
+            showGames(event.payload.games);
+            setGamesError(event.payload.error ?? '');
+        });
+
+        return () => {
+            unlisten.then((stop) => stop());
+        };
     }, []);
 
     const calculateSens = debounce(async () => {
@@ -53,14 +96,15 @@ function Converter() {
                 const destGameData = games[destGameIndex];
 
                 const response = await invoke('convert_sens', {
+                    value: sourceSens,
+                    from: sourceGameData.unit,
                     dpi: sourceDpi,
-                    sens: sourceSens,
-                    yaw1: sourceGameData.yaw,
+                    to: destGameData.unit,
                     newDpi: destDpi,
-                    yaw2: destGameData.yaw,
                 });
 
-                setNewSens(response);
+                setNewSens(response.settable_sens);
+                setConversion(response);
             }
         } catch (error) {
             console.error('Failed to calculate sens', error);
@@ -75,8 +119,53 @@ function Converter() {
         }
     }, [sourceSens, sourceDpi, destDpi, sourceGameIndex, destGameIndex]);
 
-    const isSourceCm360 = games[sourceGameIndex]?.name === 'cm/360';
-    const isDestCm360 = games[destGameIndex]?.name === 'cm/360';
+    const convertToAll = async () => {
+        try {
+            const response = await invoke('convert_to_all', {
+                sourceGame: games[sourceGameIndex].name,
+                sens: sourceSens,
+                dpi: sourceDpi,
+                newDpi: destDpi,
+            });
+            setAllGames(response);
+        } catch (error) {
+            setAllGames([]);
+            console.error('Failed to convert to all games', error);
+        }
+    };
+
+    const copyTable = async (format) => {
+        try {
+            const table = await invoke('export_conversion_table', {results: allGames, format});
+            await navigator.clipboard.writeText(table);
+        } catch (error) {
+            console.error('Failed to export table', error);
+        }
+    };
+
+    const readSourceConfig = async () => {
+        try {
+            const response = await invoke('read_game_config', {game: games[sourceGameIndex].name});
+            if (response.sens !== null) setSourceSens(response.sens);
+            setConfigMessage(response.sens !== null ? `Read from ${response.path}` : `No sensitivity in ${response.path}`);
+        } catch (error) {
+            setConfigMessage(`${error}`);
+        }
+    };
+
+    const writeDestConfig = async () => {
+        try {
+            const backup = await invoke('write_game_config', {game: games[destGameIndex].name, sens: newSens});
+            setConfigMessage(`Written, original config kept as ${backup}`);
+        } catch (error) {
+            setConfigMessage(`${error}`);
+        }
+    };
+
+    const isSourceGame = games[sourceGameIndex]?.isGame === true;
+    const isDestGame = games[destGameIndex]?.isGame === true;
+    const sourceUsesDpi = games[sourceGameIndex]?.usesDpi !== false;
+    const destUsesDpi = games[destGameIndex]?.usesDpi !== false;
 
     return (
         <div className="main-container">
@@ -93,6 +182,7 @@ function Converter() {
                     className="info-icon"
                 />
             </div>
+            {gamesError && <div>Games.json has an error, using the last good list: {gamesError}</div>}
             <div className="input-group">
                 <FormControl fullWidth>
                     <InputLabel id="source-select-label">Select Source-Game</InputLabel>
@@ -146,7 +236,7 @@ function Converter() {
                         data-tooltip-content="DPI of your mouse"
                         data-tooltip-place="bottom"
                         className="info-icon"
-                        disabled={isSourceCm360}
+                        disabled={!sourceUsesDpi}
                     />
                 </div>
             </div>
@@ -204,10 +294,49 @@ function Converter() {
                         data-tooltip-content="DPI of your mouse"
                         data-tooltip-place="bottom"
                         className="info-icon"
-                        disabled={isDestCm360}
+                        disabled={!destUsesDpi}
                     />
                 </div>
             </div>
+            {conversion && isDestGame && (
+                <div className="current-keybind">
+                    <div>Exact: {conversion.sens.toFixed(6)}</div>
+                    <div>cm/360: {conversion.cm_per360.toFixed(2)} ({conversion.error_percent.toFixed(2)}%)</div>
+                    {conversion.suggested_dpi && (
+                        <div>Closer match at {conversion.suggested_dpi} DPI</div>
+                    )}
+                </div>
+            )}
+            {(games[sourceGameIndex]?.config || games[destGameIndex]?.config) && (
+                <div className="button-container">
+                    {games[sourceGameIndex]?.config && (
+                        <Button variant="outlined" onClick={readSourceConfig}>Read from game config</Button>
+                    )}
+                    {games[destGameIndex]?.config && (
+                        <Button variant="outlined" onClick={writeDestConfig}>Write to game config</Button>
+                    )}
+                </div>
+            )}
+            {configMessage && <div>{configMessage}</div>}
+            <div className="button-container">
+                <Button variant="outlined" onClick={convertToAll}
+                        disabled={!isSourceGame}>
+                    Convert to all games
+                </Button>
+            </div>
+            {allGames.length > 0 && (
+                <div className="current-keybind">
+                    {allGames.map((game) => (
+                        <div key={game.name}>
+                            {game.name}: {game.settable_sens} ({game.error_percent.toFixed(2)}%)
+                        </div>
+                    ))}
+                    <div className="button-container">
+                        <Button variant="outlined" onClick={() => copyTable('csv')}>Copy CSV</Button>
+                        <Button variant="outlined" onClick={() => copyTable('markdown')}>Copy Markdown</Button>
+                    </div>
+                </div>
+            )}
         </div>
     );
 }
@@ -0,0 +1,133 @@
+import React, {useEffect, useState} from 'react';
+import {invoke} from '@tauri-apps/api/tauri';
+import {listen} from '@tauri-apps/api/event';
+import {Tooltip as ReactTooltip} from 'react-tooltip';
+import {FontAwesomeIcon} from '@fortawesome/react-fontawesome';
+import {faQuestionCircle} from '@fortawesome/free-solid-svg-icons';
+import Button from '@mui/material/Button';
+
+function DpiOptimizer() {
+    const [cm360, setCm360] = useState(0);
+    const [dpiSteps, setDpiSteps] = useState('400, 800, 1200, 1600, 3200');
+    const [games, setGames] = useState([]);
+    const [selectedGames, setSelectedGames] = useState([]);
+    const [candidates, setCandidates] = useState([]);
+    const [error, setError] = useState('');
+
+    useEffect(() => {
+        const fetchInitialValues = async () => {
+            try {
+                const response = await invoke('get_initial_values');
+                setCm360(response.cm360);
+                setGames(await invoke('get_games'));
+            } catch (error) {
+                console.error('Failed to fetch initial values:', error);
+            }
+        };
+
+        fetchInitialValues();
+    }, []);
+
+    useEffect(() => {
+        const unlisten = listen('games_changed', (event) => {
+            setGames(event.payload.games);
+        });
+
+        return () => {
+            unlisten.then((stop) => stop());
+        };
+    }, []);
+
+    const toggleGame = (name) => {
+        setSelectedGames((prev) =>
+            prev.includes(name) ? prev.filter((game) => game !== name) : [...prev, name]
+        );
+    };
+
+    const handleOptimize = async () => {
+        try {
+            const response = await invoke('optimize_game_dpi', {
+                cm360: parseFloat(cm360),
+                dpiSteps: dpiSteps.split(',').map((dpi) => parseInt(dpi.trim())).filter((dpi) => dpi > 0),
+                games: selectedGames,
+                limit: 10,
+            });
+            setCandidates(response);
+            setError('');
+        } catch (error) {
+            setError(`${error}`);
+        }
+    };
+
+    return (
+        <div className="main-container">
+            <ReactTooltip id="info-tooltip" className="tooltip-box"/>
+            <div className="info-container">
+                <FontAwesomeIcon icon={faQuestionCircle}
+                                 data-tooltip-id="info-tooltip"
+                                 data-tooltip-content="This page finds the DPI that matches your cm/360 best in all selected games.
+1. Enter your cm/360 and the DPI steps your mouse supports
+2. Select the games you play
+3. Press Optimize. The best DPI values are listed first"
+                                 data-tooltip-place="left" className="info-icon"/>
+            </div>
+            <div className="input-group">
+                <label htmlFor="cm360">cm/360:</label>
+                <input
+                    type="number"
+                    id="cm360"
+                    name="cm360"
+                    value={cm360}
+                    onChange={(e) => setCm360(e.target.value)}
+                />
+            </div>
+            <div className="input-group">
+                <label htmlFor="dpi-steps">DPI steps:</label>
+                <input
+                    type="text"
+                    id="dpi-steps"
+                    name="dpi-steps"
+                    value={dpiSteps}
+                    onChange={(e) => setDpiSteps(e.target.value)}
+                    data-tooltip-id="info-tooltip"
+                    data-tooltip-content="Comma separated DPI values your mouse supports"
+                    data-tooltip-place="bottom" className="info-icon"
+                />
+            </div>
+            <div className="current-keybind">
+                {games.map((game) => (
+                    <label key={game.name}>
+                        <input
+                            type="checkbox"
+                            checked={selectedGames.includes(game.name)}
+                            onChange={() => toggleGame(game.name)}
+                        />
+                        {game.name}
+                    </label>
+                ))}
+            </div>
+            <div className="button-container">
+                <Button variant="outlined" onClick={handleOptimize} disabled={selectedGames.length === 0}>
+                    Optimize
+                </Button>
+            </div>
+            <div>{error}</div>
+            {candidates.map((candidate) => (
+                <div className="current-keybind" key={candidate.dpi}>
+                    <div>
+                        {candidate.dpi} DPI: worst {candidate.worst_error_percent.toFixed(2)}%,
+                        mean {candidate.mean_error_percent.toFixed(2)}%
+                        {candidate.below_floor && ' (sensitivity too low in some games)'}
+                    </div>
+                    {candidate.games.map((game) => (
+                        <div key={game.name}>
+                            {game.name}: {game.settable_sens} ({game.error_percent.toFixed(2)}%)
+                        </div>
+                    ))}
+                </div>
+            ))}
+        </div>
+    );
+}
+
+export default DpiOptimizer;
@@ -0,0 +1,126 @@
+import React, {useEffect, useState} from 'react';
+import {invoke} from '@tauri-apps/api/tauri';
+import {listen} from '@tauri-apps/api/event';
+import debounce from 'lodash/debounce';
+import {Tooltip as ReactTooltip} from 'react-tooltip';
+import {FontAwesomeIcon} from '@fortawesome/react-fontawesome';
+import {faQuestionCircle} from '@fortawesome/free-solid-svg-icons';
+
+function Gamepad() {
+    const [games, setGames] = useState([]);
+    const [sourceGame, setSourceGame] = useState('');
+    const [destGame, setDestGame] = useState('');
+    const [sens, setSens] = useState(1);
+    const [deflection, setDeflection] = useState(1);
+    const [mouseSpeed, setMouseSpeed] = useState(10);
+    const [held, setHeld] = useState(0);
+    const [conversion, setConversion] = useState(null);
+
+    useEffect(() => {
+        invoke('get_games')
+            .then((response) => setGames(response.filter((game) => game.gamepad)))
+            .catch((error) => console.error('Failed to fetch games: ', error));
+    }, []);
+
+    useEffect(() => {
+        const unlisten = listen('games_changed', (event) => {
+            setGames(event.payload.games.filter((game) => game.gamepad));
+        });
+
+        return () => {
+            unlisten.then((stop) => stop());
+        };
+    }, []);
+
+    const convert = debounce(async () => {
+        try {
+            if (sourceGame && destGame) {
+                const response = await invoke('convert_gamepad_sens', {
+                    sourceGame,
+                    sens: parseFloat(sens),
+                    destGame,
+                    deflection: parseFloat(deflection),
+                    held: parseFloat(held),
+                    mouseSpeed: parseFloat(mouseSpeed),
+                });
+                setConversion(response);
+            }
+        } catch (error) {
+            console.error('Failed to convert gamepad sensitivity', error);
+        }
+    }, 500);
+
+    useEffect(() => {
+        convert();
+    }, [sourceGame, destGame, sens, deflection, held, mouseSpeed]);
+
+    const gameSelect = (id, value, onChange) => (
+        <select id={id} value={value} onChange={(e) => onChange(e.target.value)}>
+            <option value="">Select a game</option>
+            {games.map((game) => (
+                <option key={game.name} value={game.name}>{game.name}</option>
+            ))}
+        </select>
+    );
+
+    return (
+        <div className="main-container">
+            <ReactTooltip id="info-tooltip" className="tooltip-box"/>
+            <div className="info-container">
+                <FontAwesomeIcon icon={faQuestionCircle}
+                                 data-tooltip-id="info-tooltip"
+                                 data-tooltip-content="This page converts controller sensitivity between games.
+1. Add gamepad settings to the games in Games.json
+2. Select source and destination game and enter your sensitivity
+3. The turn rate is matched at the chosen stick deflection and hold time"
+                                 data-tooltip-place="left" className="info-icon"/>
+            </div>
+            <div className="limits-group">
+                <div className="input-group">
+                    <label htmlFor="source-game">Source game:</label>
+                    {gameSelect('source-game', sourceGame, setSourceGame)}
+                </div>
+                <div className="input-group">
+                    <label htmlFor="dest-game">Destination game:</label>
+                    {gameSelect('dest-game', destGame, setDestGame)}
+                </div>
+            </div>
+            <div className="limits-group">
+                <div className="input-group">
+                    <label htmlFor="sens">Sensitivity:</label>
+                    <input type="number" id="sens" value={sens} onChange={(e) => setSens(e.target.value)}/>
+                </div>
+                <div className="input-group">
+                    <label htmlFor="mouse-speed">Mouse speed (cm/s):</label>
+                    <input type="number" id="mouse-speed" value={mouseSpeed}
+                           onChange={(e) => setMouseSpeed(e.target.value)}
+                           data-tooltip-id="info-tooltip"
+                           data-tooltip-content="Mouse speed used for the cm/360 equivalent"
+                           data-tooltip-place="bottom" className="info-icon"/>
+                </div>
+            </div>
+            <div className="slider">
+                <label htmlFor="deflection">Stick deflection: {deflection}</label>
+                <input type="range" id="deflection" min="0.1" max="1" step="0.05" value={deflection}
+                       onChange={(e) => setDeflection(e.target.value)}/>
+            </div>
+            <div className="input-group">
+                <label htmlFor="held">Hold time (s):</label>
+                <input type="number" id="held" min="0" step="0.1" value={held}
+                       onChange={(e) => setHeld(e.target.value)}
+                       data-tooltip-id="info-tooltip"
+                       data-tooltip-content="Time the stick is held at full deflection, so turn acceleration is included"
+                       data-tooltip-place="bottom"/>
+            </div>
+            {conversion && (
+                <div className="current-keybind">
+                    <div>Sensitivity: {conversion.sens.toFixed(4)}</div>
+                    <div>Turn rate: {conversion.turn_rate.toFixed(1)} deg/s</div>
+                    <div>Mouse equivalent: {conversion.cm_per360.toFixed(2)} cm/360</div>
+                </div>
+            )}
+        </div>
+    );
+}
+
+export default Gamepad;
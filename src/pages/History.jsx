@@ -0,0 +1,130 @@
+import React, {useEffect, useState} from 'react';
+import {invoke} from '@tauri-apps/api/tauri';
+import {listen} from '@tauri-apps/api/event';
+import {Tooltip as ReactTooltip} from 'react-tooltip';
+import {FontAwesomeIcon} from '@fortawesome/react-fontawesome';
+import {faQuestionCircle} from '@fortawesome/free-solid-svg-icons';
+import Select from '@mui/material/Select';
+import MenuItem from '@mui/material/MenuItem';
+import InputLabel from '@mui/material/InputLabel';
+import FormControl from '@mui/material/FormControl';
+import Button from '@mui/material/Button';
+
+const KIND_LABELS = {
+    cm360: 'cm/360',
+    fov: 'FOV',
+    yaw: 'Yaw',
+};
+
+function History() {
+    const [records, setRecords] = useState([]);
+    const [kind, setKind] = useState('');
+    const [game, setGame] = useState('');
+    const [device, setDevice] = useState('');
+    const [revision, setRevision] = useState(0);
+
+    const filter = () => ({
+        kind: kind || null,
+        game: game || null,
+        device: device || null,
+    });
+
+    useEffect(() => {
+        invoke('get_measurement_history', {filter: filter()})
+            .then((response) => setRecords([...response].reverse()))
+            .catch((error) => {
+                console.error('Failed to load measurement history:', error);
+            });
+    }, [kind, game, device, revision]);
+
+    useEffect(() => {
+        const unlisten = listen('measurement_recorded', () => {
+            setRevision((current) => current + 1);
+        });
+
+        return () => {
+            unlisten.then((stop) => stop());
+        };
+    }, []);
+
+    const copyHistory = async (format) => {
+        try {
+            const table = await invoke('export_measurement_history', {filter: filter(), format});
+            await navigator.clipboard.writeText(table);
+        } catch (error) {
+            console.error('Failed to export history', error);
+        }
+    };
+
+    const formatValue = (record) => {
+        switch (record.kind) {
+            case 'cm360':
+                return `${record.value.toFixed(2)} cm/360`;
+            case 'fov':
+                return `FOV ${record.value.toFixed(2)}`;
+            default:
+                return `yaw ${record.value.toFixed(6)}`;
+        }
+    };
+
+    return (
+        <div className="main-container">
+            <ReactTooltip id="info-tooltip" className="tooltip-box"/>
+            <div className="info-container">
+                <FontAwesomeIcon icon={faQuestionCircle}
+                                 data-tooltip-id="info-tooltip"
+                                 data-tooltip-content="This page lists every completed measurement, newest first.
+Each entry keeps the mouse counts and the profile that were used, so results can be compared over time.
+Filter by type, game or mouse and copy the list as CSV or Markdown."
+                                 data-tooltip-place="left" className="info-icon"/>
+            </div>
+            <div className="limits-group">
+                <div className="input-group">
+                    <FormControl fullWidth>
+                        <InputLabel id="kind-select-label">Type</InputLabel>
+                        <Select
+                            labelId="kind-select-label"
+                            id="kind-select"
+                            value={kind}
+                            onChange={(e) => setKind(e.target.value)}
+                            label="Type"
+                        >
+                            <MenuItem value="">All</MenuItem>
+                            {Object.entries(KIND_LABELS).map(([value, label]) => (
+                                <MenuItem key={value} value={value}>
+                                    {label}
+                                </MenuItem>
+                            ))}
+                        </Select>
+                    </FormControl>
+                </div>
+                <div className="input-group">
+                    <label htmlFor="game">Game:</label>
+                    <input type="text" id="game" value={game}
+                           onChange={(e) => setGame(e.target.value)}/>
+                </div>
+                <div className="input-group">
+                    <label htmlFor="device">Mouse:</label>
+                    <input type="text" id="device" value={device}
+                           onChange={(e) => setDevice(e.target.value)}/>
+                </div>
+            </div>
+            <div className="input-group">
+                <Button variant="outlined" onClick={() => copyHistory('csv')}>Copy CSV</Button>
+                <Button variant="outlined" onClick={() => copyHistory('markdown')}>Copy Markdown</Button>
+            </div>
+            <div className="current-keybind">
+                {records.length === 0 && <div>No measurements yet</div>}
+                {records.map((record, index) => (
+                    <div key={`${record.timestamp}-${index}`}>
+                        {new Date(record.timestamp * 1000).toLocaleString()} - {KIND_LABELS[record.kind]}
+                        {record.game && ` (${record.game})`}: {formatValue(record)}, {record.counts} counts
+                        at {record.profile.dpi} DPI{record.device && ` with ${record.device}`}
+                    </div>
+                ))}
+            </div>
+        </div>
+    );
+}
+
+export default History;
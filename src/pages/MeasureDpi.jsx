@@ -0,0 +1,171 @@
+import React, {useCallback, useEffect, useRef, useState} from 'react';
+import {invoke} from '@tauri-apps/api/tauri';
+import {listen} from '@tauri-apps/api/event';
+import debounce from 'lodash/debounce';
+import {Tooltip as ReactTooltip} from 'react-tooltip';
+import {FontAwesomeIcon} from '@fortawesome/react-fontawesome';
+import {faQuestionCircle} from '@fortawesome/free-solid-svg-icons';
+import Button from '@mui/material/Button';
+
+function MeasureDpi() {
+    const [dpi, setDpi] = useState(0);
+    const [distance, setDistance] = useState(0);
+    const [samples, setSamples] = useState([]);
+    const [effectiveDpi, setEffectiveDpi] = useState(0);
+    const [spread, setSpread] = useState(0);
+    const [correction, setCorrection] = useState(1);
+
+    const isInitialMount = useRef(true);
+
+    const applyValues = (values) => {
+        setDpi(values.dpi);
+        setDistance(values.distance);
+        setSamples(values.samples);
+        setEffectiveDpi(values.effective_dpi);
+        setSpread(values.spread);
+    };
+
+    useEffect(() => {
+        const fetchInitialValues = async () => {
+            try {
+                await startListener();
+                const response = await invoke('get_dpi_values');
+                applyValues(response);
+                const settings = await invoke('get_app_settings');
+                setCorrection(settings.dpi_correction);
+            } catch (error) {
+                console.error('Failed to fetch initial values:', error);
+            }
+        };
+
+        fetchInitialValues();
+    }, []);
+
+    const debouncedUpdateValues = useCallback(
+        debounce((dpi, distance) => {
+            invoke('set_dpi_values', {
+                dpi: parseInt(dpi),
+                distance: parseFloat(distance)
+            }).then(applyValues).catch((error) => {
+                console.error('Failed to set dpi values:', error);
+            });
+        }, 500),
+        []
+    );
+
+    useEffect(() => {
+        if (isInitialMount.current) {
+            isInitialMount.current = false;
+        } else {
+            debouncedUpdateValues(dpi, distance);
+        }
+    }, [dpi, distance, debouncedUpdateValues]);
+
+    async function startListener() {
+        await listen('dpi_update', (event) => {
+            applyValues(event.payload);
+        });
+    }
+
+    const handleReset = async () => {
+        try {
+            applyValues(await invoke('reset_dpi_values'));
+        } catch (error) {
+            console.error('Failed to reset dpi values:', error);
+        }
+    };
+
+    const handleSave = async () => {
+        try {
+            setCorrection(await invoke('save_dpi_correction'));
+        } catch (error) {
+            console.error('Failed to save dpi correction:', error);
+        }
+    };
+
+    const handleClear = async () => {
+        try {
+            await invoke('clear_dpi_correction');
+            setCorrection(1);
+        } catch (error) {
+            console.error('Failed to clear dpi correction:', error);
+        }
+    };
+
+    return (
+        <div className="main-container">
+            <ReactTooltip id="info-tooltip" className="tooltip-box"/>
+            <div className="info-container">
+                <FontAwesomeIcon icon={faQuestionCircle}
+                                 data-tooltip-id="info-tooltip"
+                                 data-tooltip-content="This page lets you check the real DPI of your mouse.
+1. Enter the DPI set in your mouse software and the distance you will move along a ruler
+2. Press hotkey 1, move the mouse exactly that distance and press hotkey 1 again
+3. Repeat step 2 a few times. Press hotkey 2 to drop the last pass
+4. Save the result to correct all calculations for your real DPI"
+                                 data-tooltip-place="left" className="info-icon"/>
+            </div>
+            <div className="input-group">
+                <label htmlFor="dpi">DPI:</label>
+                <input
+                    type="number"
+                    id="dpi"
+                    name="dpi"
+                    value={dpi}
+                    onChange={(e) => setDpi(parseInt(e.target.value))}
+                    data-tooltip-id="info-tooltip"
+                    data-tooltip-content="DPI set in your mouse software"
+                    data-tooltip-place="bottom" className="info-icon"
+                />
+            </div>
+            <div className="input-group">
+                <label htmlFor="distance">Distance (cm):</label>
+                <input
+                    type="number"
+                    id="distance"
+                    name="distance"
+                    value={distance}
+                    onChange={(e) => setDistance(parseFloat(e.target.value))}
+                    data-tooltip-id="info-tooltip"
+                    data-tooltip-content="Distance you move the mouse along the ruler"
+                    data-tooltip-place="bottom" className="info-icon"
+                />
+            </div>
+            <div className="limits-group">
+                <div className="input-group">
+                    <label htmlFor="effective-dpi">Effective DPI:</label>
+                    <input
+                        type="number"
+                        id="effective-dpi"
+                        name="effective-dpi"
+                        value={effectiveDpi.toFixed(1)}
+                        readOnly
+                        data-tooltip-id="info-tooltip"
+                        data-tooltip-content={`Average of ${samples.length} passes`}
+                        data-tooltip-place="top" className="info-icon"
+                    />
+                </div>
+                <div className="input-group">
+                    <label htmlFor="spread">Spread:</label>
+                    <input
+                        type="number"
+                        id="spread"
+                        name="spread"
+                        value={spread.toFixed(1)}
+                        readOnly
+                        data-tooltip-id="info-tooltip"
+                        data-tooltip-content="Standard deviation between passes"
+                        data-tooltip-place="top" className="info-icon"
+                    />
+                </div>
+            </div>
+            <div className="button-container">
+                <Button variant="outlined" onClick={handleReset}>Reset</Button>
+                <Button variant="outlined" onClick={handleSave}>Save</Button>
+                <Button variant="outlined" onClick={handleClear}>Clear ({correction.toFixed(3)})</Button>
+            </div>
+        </div>
+    );
+}
+
+export default MeasureDpi;
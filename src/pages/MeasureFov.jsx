@@ -13,6 +13,8 @@ function MeasureFov() {
     const [fovHorizontal, setFovHorizontal] = useState(0);
     const [fov4ML3, setFov4ML3] = useState(0);
     const [fovVertical, setFovVertical] = useState(0);
+    const [games, setGames] = useState([]);
+    const [game, setGame] = useState('');
 
     const isInitialMount = useRef(true);
 
@@ -27,6 +29,8 @@ function MeasureFov() {
                 setFovHorizontal(response.game_fov);
                 setFov4ML3(horizontalTo4ML3(response.game_fov));
                 setFovVertical(horizontalToVertical(response.game_fov));
+                setGame(response.game || '');
+                setGames(await invoke('get_games'));
             } catch (error) {
                 console.error('Failed to fetch initial values:', error);
             }
@@ -35,6 +39,23 @@ function MeasureFov() {
         fetchInitialValues();
     }, []);
 
+    useEffect(() => {
+        const unlisten = listen('games_changed', (event) => {
+            setGames(event.payload.games);
+        });
+
+        return () => {
+            unlisten.then((stop) => stop());
+        };
+    }, []);
+
+    const handleGameChange = (name) => {
+        setGame(name);
+        invoke('set_measured_game', {game: name}).catch((error) => {
+            console.error('Failed to set the measured game:', error);
+        });
+    };
+
     const updateSettings = debounce((cm360, dpi, gameSens, gameFov) => {
         invoke('set_user_settings', {
             cm360: parseFloat(cm360),
@@ -140,6 +161,18 @@ IMPORTANT: For the conversion to be accurate, have AimCalibrate on the screen yo
                     data-tooltip-place="bottom" className="info-icon"
                 />
             </div>
+            <div className="input-group">
+                <label htmlFor="measured-game">Game:</label>
+                <select id="measured-game" value={game} onChange={(e) => handleGameChange(e.target.value)}
+                        data-tooltip-id="info-tooltip"
+                        data-tooltip-content="Game the measurements are recorded for in the history"
+                        data-tooltip-place="bottom">
+                    <option value="">No game</option>
+                    {games.map((g) => (
+                        <option key={g.name} value={g.name}>{g.name}</option>
+                    ))}
+                </select>
+            </div>
             <div className="input-group">
                 <label htmlFor="gameSens">Game Sens:</label>
                 <input
@@ -19,6 +19,9 @@ function MeasureYaw() {
     const [upperLimit, setUpperLimit] = useState(0);
     const [isDialogOpen, setIsDialogOpen] = useState(false);
     const [dialogText, setDialogText] = useState('');
+    const [rawInput, setRawInput] = useState(true);
+    const [games, setGames] = useState([]);
+    const [game, setGame] = useState('');
 
     const isInitialMount = useRef(true);
 
@@ -32,6 +35,8 @@ function MeasureYaw() {
                 setYaw(response.yaw);
                 setLowerLimit(response.lower_limit);
                 setUpperLimit(response.upper_limit);
+                setGame((await invoke('get_initial_values')).game || '');
+                setGames(await invoke('get_games'));
             } catch (error) {
                 console.error('Failed to fetch initial values:', error);
             }
@@ -40,6 +45,23 @@ function MeasureYaw() {
         fetchInitialValues();
     }, []);
 
+    useEffect(() => {
+        const unlisten = listen('games_changed', (event) => {
+            setGames(event.payload.games);
+        });
+
+        return () => {
+            unlisten.then((stop) => stop());
+        };
+    }, []);
+
+    const handleGameChange = (name) => {
+        setGame(name);
+        invoke('set_measured_game', {game: name}).catch((error) => {
+            console.error('Failed to set the measured game:', error);
+        });
+    };
+
     const handleSensChange = async (sens) => {
         setSens(sens);
         try {
@@ -72,7 +94,8 @@ function MeasureYaw() {
         console.log('Dialog text saved:', dialogText);
         invoke('save_game_yaw', {
             name: dialogText,
-            yaw: yaw
+            yaw: yaw,
+            rawInput: rawInput
         })
         closeDialog();
     };
@@ -91,6 +114,18 @@ function MeasureYaw() {
 5. Repeat step 3 and 4 until it turns exactly 360 degrees. The value shown in the textbox is the yaw value"
                                  data-tooltip-place="bottom" className="info-icon"/>
             </div>
+            <div className="input-group">
+                <label htmlFor="measured-game">Game:</label>
+                <select id="measured-game" value={game} onChange={(e) => handleGameChange(e.target.value)}
+                        data-tooltip-id="info-tooltip"
+                        data-tooltip-content="Game the measurements are recorded for in the history"
+                        data-tooltip-place="bottom">
+                    <option value="">No game</option>
+                    {games.map((g) => (
+                        <option key={g.name} value={g.name}>{g.name}</option>
+                    ))}
+                </select>
+            </div>
             <div className="input-group">
                 <label htmlFor="sens">Sens:</label>
                 <input
@@ -171,6 +206,14 @@ function MeasureYaw() {
                             },
                         }}
                     />
+                    <label>
+                        <input
+                            type="checkbox"
+                            checked={rawInput}
+                            onChange={(e) => setRawInput(e.target.checked)}
+                        />
+                        Game uses raw input
+                    </label>
                 </DialogContent>
                 <DialogActions style={{backgroundColor: '#1f1f1f'}}>
                     <Button onClick={closeDialog} style={{color: '#007bff'}}>Cancel</Button>
@@ -0,0 +1,86 @@
+import React, {useEffect, useState} from 'react';
+import {listen} from '@tauri-apps/api/event';
+import {Tooltip as ReactTooltip} from 'react-tooltip';
+import {FontAwesomeIcon} from '@fortawesome/react-fontawesome';
+import {faQuestionCircle} from '@fortawesome/free-solid-svg-icons';
+
+function PollingRate() {
+    const [report, setReport] = useState(null);
+
+    useEffect(() => {
+        let unlisten;
+
+        const startListener = async () => {
+            unlisten = await listen('polling_update', (event) => {
+                setReport(event.payload);
+            });
+        };
+
+        startListener();
+
+        return () => {
+            if (unlisten) unlisten();
+        };
+    }, []);
+
+    return (
+        <div className="main-container">
+            <ReactTooltip id="info-tooltip" className="tooltip-box"/>
+            <div className="info-container">
+                <FontAwesomeIcon icon={faQuestionCircle}
+                                 data-tooltip-id="info-tooltip"
+                                 data-tooltip-content="This page checks the polling rate of your mouse.
+1. Press hotkey 1
+2. Move your mouse quickly in circles for a few seconds
+3. Press hotkey 1 again to see the results"
+                                 data-tooltip-place="left" className="info-icon"/>
+            </div>
+            <div className="limits-group">
+                <div className="input-group">
+                    <label htmlFor="polling-rate">Polling rate (Hz):</label>
+                    <input type="number" id="polling-rate" readOnly
+                           value={report ? report.polling_rate.toFixed(0) : '-'}/>
+                </div>
+                <div className="input-group">
+                    <label htmlFor="nominal-rate">Nominal rate (Hz):</label>
+                    <input type="number" id="nominal-rate" readOnly
+                           value={report ? report.nominal_rate : '-'}/>
+                </div>
+            </div>
+            <div className="limits-group">
+                <div className="input-group">
+                    <label htmlFor="mean-interval">Mean interval (µs):</label>
+                    <input type="number" id="mean-interval" readOnly
+                           value={report ? report.mean_interval_us.toFixed(1) : '-'}/>
+                </div>
+                <div className="input-group">
+                    <label htmlFor="jitter">Jitter (µs):</label>
+                    <input type="number" id="jitter" readOnly
+                           value={report ? report.jitter_us.toFixed(1) : '-'}/>
+                </div>
+            </div>
+            <div className="limits-group">
+                <div className="input-group">
+                    <label htmlFor="irregular">Irregular intervals:</label>
+                    <input type="number" id="irregular" readOnly
+                           value={report ? report.irregular_intervals : '-'}/>
+                </div>
+                <div className="input-group">
+                    <label htmlFor="dropped">Dropped packets:</label>
+                    <input type="number" id="dropped" readOnly
+                           value={report ? report.dropped_packets : '-'}/>
+                </div>
+            </div>
+            {report && (
+                <div className="current-keybind">
+                    Counts per packet ({report.packets} packets):
+                    {report.counts_distribution.map(([counts, packets]) => (
+                        <div key={counts}>{counts}: {packets}</div>
+                    ))}
+                </div>
+            )}
+        </div>
+    );
+}
+
+export default PollingRate;
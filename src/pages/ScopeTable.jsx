@@ -0,0 +1,128 @@
+import React, {useEffect, useState} from 'react';
+import {invoke} from '@tauri-apps/api/tauri';
+import {listen} from '@tauri-apps/api/event';
+import {Tooltip as ReactTooltip} from 'react-tooltip';
+import {FontAwesomeIcon} from '@fortawesome/react-fontawesome';
+import {faQuestionCircle} from '@fortawesome/free-solid-svg-icons';
+import Select from '@mui/material/Select';
+import MenuItem from '@mui/material/MenuItem';
+import InputLabel from '@mui/material/InputLabel';
+import FormControl from '@mui/material/FormControl';
+
+function ScopeTable() {
+    const [games, setGames] = useState([]);
+    const [game, setGame] = useState('');
+    const [cm360, setCm360] = useState(0);
+    const [dpi, setDpi] = useState(0);
+    const [normalFov, setNormalFov] = useState(0);
+    const [table, setTable] = useState(null);
+    const [error, setError] = useState('');
+
+    useEffect(() => {
+        const fetchInitialValues = async () => {
+            try {
+                const response = await invoke('get_initial_values');
+                setCm360(response.cm360);
+                setDpi(response.dpi);
+                setNormalFov(response.normal_fov);
+                const allGames = await invoke('get_games');
+                setGames(allGames.filter((g) => g.scopes && g.scopes.length > 0));
+            } catch (error) {
+                console.error('Failed to fetch initial values:', error);
+            }
+        };
+
+        fetchInitialValues();
+    }, []);
+
+    useEffect(() => {
+        const unlisten = listen('games_changed', (event) => {
+            setGames(event.payload.games.filter((g) => g.scopes && g.scopes.length > 0));
+        });
+
+        return () => {
+            unlisten.then((stop) => stop());
+        };
+    }, []);
+
+    useEffect(() => {
+        if (!game) return;
+
+        invoke('get_scope_table', {
+            game,
+            cm360: parseFloat(cm360),
+            dpi: parseInt(dpi),
+            hipfireFov: parseFloat(normalFov),
+        }).then((response) => {
+            setTable(response);
+            setError('');
+        }).catch((error) => {
+            setTable(null);
+            setError(error);
+            console.error('Failed to calculate scope table:', error);
+        });
+    }, [game, cm360, dpi, normalFov]);
+
+    return (
+        <div className="main-container">
+            <ReactTooltip id="info-tooltip" className="tooltip-box"/>
+            <div className="info-container">
+                <FontAwesomeIcon icon={faQuestionCircle}
+                                 data-tooltip-id="info-tooltip"
+                                 data-tooltip-content="This page lists the scope sensitivities for every scope of a game.
+1. Add scopes to the game in Games.json
+2. Select the game and enter your cm/360, DPI and hipfire FOV
+3. Enter the values from the table in game"
+                                 data-tooltip-place="left" className="info-icon"/>
+            </div>
+            <div className="input-group">
+                <FormControl fullWidth>
+                    <InputLabel id="game-select-label">Select Game</InputLabel>
+                    <Select
+                        labelId="game-select-label"
+                        id="game-select"
+                        value={game}
+                        onChange={(e) => setGame(e.target.value)}
+                        label="Select Game"
+                    >
+                        {games.map((g) => (
+                            <MenuItem key={g.name} value={g.name}>
+                                {g.name}
+                            </MenuItem>
+                        ))}
+                    </Select>
+                </FormControl>
+            </div>
+            <div className="limits-group">
+                <div className="input-group">
+                    <label htmlFor="cm360">cm/360:</label>
+                    <input type="number" id="cm360" value={cm360}
+                           onChange={(e) => setCm360(e.target.value)}/>
+                </div>
+                <div className="input-group">
+                    <label htmlFor="dpi">DPI:</label>
+                    <input type="number" id="dpi" value={dpi}
+                           onChange={(e) => setDpi(e.target.value)}/>
+                </div>
+                <div className="input-group">
+                    <label htmlFor="normalFov">Hipfire FOV:</label>
+                    <input type="number" id="normalFov" value={normalFov}
+                           onChange={(e) => setNormalFov(e.target.value)}/>
+                </div>
+            </div>
+            {error && <div>{error}</div>}
+            {table && (
+                <div className="current-keybind">
+                    <div>Hipfire sensitivity: {table.hipfire_sens.toFixed(4)}</div>
+                    {table.rows.map((row) => (
+                        <div key={row.name}>
+                            {row.name}: {row.sens.toFixed(4)} (FOV {row.fov.toFixed(1)}, {row.cm_per360.toFixed(2)} cm/360)
+                        </div>
+                    ))}
+                </div>
+            )}
+        </div>
+    );
+}
+
+export default ScopeTable;
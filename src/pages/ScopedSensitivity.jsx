@@ -10,6 +10,9 @@ function ScopedSensitivity() {
     const [dpi, setDpi] = useState(0);
     const [normalFov, setNormalFov] = useState(0);
     const [scopedFov, setScopedFov] = useState(0);
+    const [gameSens, setGameSens] = useState(0);
+    const [convention, setConvention] = useState('multiplier');
+    const [scopeSens, setScopeSens] = useState(0);
 
     const isInitialMount = useRef(true);
 
@@ -22,6 +25,7 @@ function ScopedSensitivity() {
                 setDpi(response.dpi);
                 setNormalFov(response.normal_fov);
                 setScopedFov(response.scoped_fov);
+                setGameSens(response.game_sens);
             } catch (error) {
                 console.error('Failed to fetch initial values:', error);
             }
@@ -51,6 +55,19 @@ function ScopedSensitivity() {
         }
     }, [cm360, dpi, normalFov, scopedFov]);
 
+    useEffect(() => {
+        if (!normalFov || !scopedFov) return;
+
+        invoke('calculate_scope_sens', {
+            convention,
+            hipfireFov: parseFloat(normalFov),
+            scopedFov: parseFloat(scopedFov),
+            hipfireSens: gameSens ? parseFloat(gameSens) : null,
+        }).then(setScopeSens).catch((error) => {
+            console.error('Failed to calculate scope sensitivity:', error);
+        });
+    }, [convention, normalFov, scopedFov, gameSens]);
+
     return (
         <div className="main-container">
             <ReactTooltip id="info-tooltip" className="tooltip-box"/>
@@ -115,6 +132,30 @@ function ScopedSensitivity() {
                     data-tooltip-place="top" className="info-icon"
                 />
             </div>
+            <div className="limits-group">
+                <div className="input-group">
+                    <label htmlFor="convention">Game uses:</label>
+                    <select id="convention" value={convention} onChange={(e) => setConvention(e.target.value)}>
+                        <option value="multiplier">Multiplier of hipfire</option>
+                        <option value="absolute">Separate sensitivity</option>
+                        <option value="relative_to_fov">Relative to FOV (%)</option>
+                        <option value="coefficient">Monitor distance coefficient</option>
+                    </select>
+                </div>
+                <div className="input-group">
+                    <label htmlFor="scopeSens">Scope sensitivity:</label>
+                    <input
+                        type="number"
+                        id="scopeSens"
+                        name="scopeSens"
+                        value={scopeSens.toFixed(4)}
+                        readOnly
+                        data-tooltip-id="info-tooltip"
+                        data-tooltip-content="Value to enter in game. Separate sensitivity uses the hipfire sensitivity from the Measure FOV tab."
+                        data-tooltip-place="top" className="info-icon"
+                    />
+                </div>
+            </div>
         </div>
     );
 }
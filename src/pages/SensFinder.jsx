@@ -0,0 +1,157 @@
+import React, {useEffect, useState} from 'react';
+import {invoke} from '@tauri-apps/api/tauri';
+import {listen} from '@tauri-apps/api/event';
+import {Tooltip as ReactTooltip} from 'react-tooltip';
+import {FontAwesomeIcon} from '@fortawesome/react-fontawesome';
+import {faQuestionCircle} from '@fortawesome/free-solid-svg-icons';
+import Button from '@mui/material/Button';
+
+function SensFinder() {
+    const [games, setGames] = useState([]);
+    const [game, setGame] = useState('');
+    const [dpi, setDpi] = useState(800);
+    const [lowerLimit, setLowerLimit] = useState(20);
+    const [upperLimit, setUpperLimit] = useState(80);
+    const [finder, setFinder] = useState(null);
+    const [error, setError] = useState('');
+
+    useEffect(() => {
+        let unlisten;
+        let unlistenGames;
+
+        const fetchInitialValues = async () => {
+            try {
+                const response = await invoke('get_initial_values');
+                if (response.dpi > 0) setDpi(response.dpi);
+                setGames(await invoke('get_games'));
+                setFinder(await invoke('get_sens_finder'));
+                unlisten = await listen('sens_finder_update', (event) => {
+                    setFinder(event.payload);
+                });
+                unlistenGames = await listen('games_changed', (event) => {
+                    setGames(event.payload.games);
+                });
+            } catch (error) {
+                console.error('Failed to fetch initial values:', error);
+            }
+        };
+
+        fetchInitialValues();
+
+        return () => {
+            if (unlisten) unlisten();
+            if (unlistenGames) unlistenGames();
+        };
+    }, []);
+
+    const handleStart = async () => {
+        try {
+            const selected = games.find((g) => g.name === game);
+            const response = await invoke('start_sens_finder', {
+                lowerLimit: parseFloat(lowerLimit),
+                upperLimit: parseFloat(upperLimit),
+                yaw: selected.yaw,
+                dpi: parseInt(dpi),
+            });
+            setFinder(response);
+            setError('');
+        } catch (error) {
+            setError(`${error}`);
+        }
+    };
+
+    const handleChoose = async (choice) => {
+        setFinder(await invoke('choose_sens', {choice}));
+    };
+
+    const handleUndo = async () => {
+        setFinder(await invoke('undo_sens_choice'));
+    };
+
+    const handleApply = async () => {
+        await invoke('set_user_settings', {cm360: finder.result, dpi: finder.dpi});
+    };
+
+    return (
+        <div className="main-container">
+            <ReactTooltip id="info-tooltip" className="tooltip-box"/>
+            <div className="info-container">
+                <FontAwesomeIcon icon={faQuestionCircle}
+                                 data-tooltip-id="info-tooltip"
+                                 data-tooltip-content="This page helps you find the cm/360 that feels best.
+1. Select a game, enter your DPI and the cm/360 range to search and press Start
+2. Set the shown sensitivity in game and play a bit. Hotkey 1 turns 360 to check it
+3. Hotkey 2 switches to the other sensitivity, try that one as well
+4. Hotkey 3 keeps the one you are using, hotkey 4 undoes the last choice"
+                                 data-tooltip-place="left" className="info-icon"/>
+            </div>
+            <div className="input-group">
+                <label htmlFor="game">Game:</label>
+                <select id="game" value={game} onChange={(e) => setGame(e.target.value)}>
+                    <option value="">Select a game</option>
+                    {games.map((g) => (
+                        <option key={g.name} value={g.name}>{g.name}</option>
+                    ))}
+                </select>
+            </div>
+            <div className="limits-group">
+                <div className="input-group">
+                    <label htmlFor="dpi">DPI:</label>
+                    <input type="number" id="dpi" value={dpi} onChange={(e) => setDpi(e.target.value)}/>
+                </div>
+                <div className="input-group">
+                    <label htmlFor="lower-limit">Lowest cm/360:</label>
+                    <input type="number" id="lower-limit" value={lowerLimit}
+                           onChange={(e) => setLowerLimit(e.target.value)}/>
+                </div>
+                <div className="input-group">
+                    <label htmlFor="upper-limit">Highest cm/360:</label>
+                    <input type="number" id="upper-limit" value={upperLimit}
+                           onChange={(e) => setUpperLimit(e.target.value)}/>
+                </div>
+            </div>
+            <div className="button-container">
+                <Button variant="outlined" onClick={handleStart} disabled={!game}>Start</Button>
+            </div>
+            <div>{error}</div>
+            {finder && finder.result === null && (
+                <div className="current-keybind">
+                    <div>Round {finder.round + 1} of {finder.rounds}</div>
+                    {finder.candidates.map((cm, index) => (
+                        <div key={index}>
+                            {index === finder.active ? '> ' : ''}
+                            {cm.toFixed(2)} cm/360, in-game {finder.game_sens[index].toFixed(4)}
+                        </div>
+                    ))}
+                    <div className="button-container">
+                        <Button variant="outlined" onClick={() => handleChoose(0)}>Faster</Button>
+                        <Button variant="outlined" onClick={() => handleChoose(1)}>Slower</Button>
+                    </div>
+                </div>
+            )}
+            {finder && finder.result !== null && (
+                <div className="current-keybind">
+                    <div>Preferred: {finder.result.toFixed(2)} cm/360</div>
+                    <div className="button-container">
+                        <Button variant="outlined" onClick={handleApply}>Use as main sensitivity</Button>
+                    </div>
+                </div>
+            )}
+            {finder && finder.choices.length > 0 && (
+                <div className="current-keybind">
+                    {finder.choices.map((choice) => (
+                        <div key={choice.round}>
+                            Round {choice.round}: {choice.candidates[0].toFixed(2)} vs {choice.candidates[1].toFixed(2)},
+                            chose {choice.chosen.toFixed(2)}
+                        </div>
+                    ))}
+                    <div className="button-container">
+                        <Button variant="outlined" onClick={handleUndo}>Undo</Button>
+                    </div>
+                </div>
+            )}
+        </div>
+    );
+}
+
+export default SensFinder;
@@ -11,18 +11,29 @@ function Settings() {
     });
     const [settingHotkey, setSettingHotkey] = useState(null);
     const [sliderValue, setSliderValue] = useState(1);
+    const [pointerSpeed, setPointerSpeed] = useState(10);
+    const [traceName, setTraceName] = useState('');
+    const [traceStatus, setTraceStatus] = useState('');
+    const [gameFolders, setGameFolders] = useState('');
+    const [installedGames, setInstalledGames] = useState([]);
+    const [api, setApi] = useState({enabled: false, port: 43110, token: ''});
+    const [storage, setStorage] = useState(null);
 
     useEffect(() => {
         const fetchInitialValues = async () => {
             try {
                 const response = await invoke('get_app_settings');
                 setSliderValue(response.turn_speed.toFixed(1));
+                setPointerSpeed(response.pointer_speed);
+                setGameFolders(response.game_folders.join('; '));
+                setApi({enabled: response.api_enabled, port: response.api_port, token: response.api_token});
                 setHotkeys({
                     hotkey1: response.hotkeys.at(0),
                     hotkey2: response.hotkeys.at(1),
                     hotkey3: response.hotkeys.at(2),
                     hotkey4: response.hotkeys.at(3)
                 });
+                setStorage(await invoke('get_storage_context'));
             } catch (error) {
                 console.error('Failed to fetch initial values:', error);
             }
@@ -32,14 +43,16 @@ function Settings() {
     }, []);
 
     useEffect(() => {
-        debouncedUpdateSettings(sliderValue, hotkeys)
-    }, [hotkeys, sliderValue]);
+        debouncedUpdateSettings(sliderValue, hotkeys, pointerSpeed, gameFolders)
+    }, [hotkeys, sliderValue, pointerSpeed, gameFolders]);
 
     const debouncedUpdateSettings = useCallback(
-        debounce((sliderValue, hotkeys) => {
+        debounce((sliderValue, hotkeys, pointerSpeed, gameFolders) => {
             invoke('set_app_settings', {
                 turnSpeed: parseFloat(sliderValue),
-                hotkeys: [hotkeys.hotkey1, hotkeys.hotkey2, hotkeys.hotkey3, hotkeys.hotkey4]
+                hotkeys: [hotkeys.hotkey1, hotkeys.hotkey2, hotkeys.hotkey3, hotkeys.hotkey4],
+                pointerSpeed: parseInt(pointerSpeed),
+                gameFolders: gameFolders.split(';').map((folder) => folder.trim()).filter((folder) => folder)
             }).catch((error) => {
                 console.error('Failed to set user settings:', error);
             });
@@ -58,6 +71,32 @@ function Settings() {
         }));
     };
 
+    const handleSaveTrace = async () => {
+        try {
+            const path = await invoke('save_movement_trace', {name: traceName});
+            setTraceStatus(`Saved to ${path}`);
+        } catch (error) {
+            setTraceStatus(`${error}`);
+        }
+    };
+
+    const handleFindGames = async () => {
+        try {
+            setInstalledGames(await invoke('discover_installed_games'));
+        } catch (error) {
+            console.error('Failed to find installed games:', error);
+        }
+    };
+
+    const updateApi = async (changes) => {
+        try {
+            const response = await invoke('set_api_settings', changes);
+            setApi({enabled: response.api_enabled, port: response.api_port, token: response.api_token});
+        } catch (error) {
+            console.error('Failed to set control API settings:', error);
+        }
+    };
+
     const handleKeyPress = (event) => {
         if (settingHotkey) {
             event.preventDefault(); // Prevent default action to avoid conflicts
@@ -114,6 +153,83 @@ function Settings() {
                     onChange={handleSliderChange}
                 />
             </div>
+            <div className="slider">
+                <label htmlFor="pointer-speed-slider">Windows pointer speed: {pointerSpeed} (default 10)</label>
+                <input
+                    type="range"
+                    id="pointer-speed-slider"
+                    min="1"
+                    max="20"
+                    step="1"
+                    value={pointerSpeed}
+                    onChange={(e) => setPointerSpeed(e.target.value)}
+                />
+            </div>
+            <div className="input-group">
+                <label htmlFor="trace-name">Save last tracking session as trace:</label>
+                <input
+                    type="text"
+                    id="trace-name"
+                    name="trace-name"
+                    value={traceName}
+                    onChange={(e) => setTraceName(e.target.value)}
+                />
+                <button className="keybind-button" onClick={handleSaveTrace} disabled={!traceName}>
+                    Save trace
+                </button>
+                <div>{traceStatus}</div>
+            </div>
+            <div className="input-group">
+                <label htmlFor="game-folders">Other game folders (separated by ;):</label>
+                <input
+                    type="text"
+                    id="game-folders"
+                    name="game-folders"
+                    value={gameFolders}
+                    onChange={(e) => setGameFolders(e.target.value)}
+                />
+                <button className="keybind-button" onClick={handleFindGames}>
+                    Find installed games
+                </button>
+            </div>
+            <div className="input-group">
+                <label htmlFor="api-enabled">
+                    <input
+                        type="checkbox"
+                        id="api-enabled"
+                        checked={api.enabled}
+                        onChange={(e) => updateApi({enabled: e.target.checked})}
+                    />
+                    Local control API
+                </label>
+                <label htmlFor="api-port">Port:</label>
+                <input
+                    type="number"
+                    id="api-port"
+                    name="api-port"
+                    value={api.port}
+                    onChange={(e) => setApi({...api, port: e.target.value})}
+                    onBlur={() => updateApi({port: parseInt(api.port)})}
+                />
+                {api.token && <div>Token: {api.token}</div>}
+                <button className="keybind-button" onClick={() => updateApi({newToken: true})}>
+                    New token
+                </button>
+            </div>
+            {installedGames.length > 0 && (
+                <div className="current-keybind">
+                    {installedGames.map((game) => (
+                        <div key={game.install_dir}>
+                            {game.name}{game.matched ? ` (${game.matched})` : ''}: {game.install_dir}
+                        </div>
+                    ))}
+                </div>
+            )}
+            {storage && (
+                <div className="current-keybind">
+                    Data folder{storage.portable ? ' (portable)' : ''}: {storage.config_dir}
+                </div>
+            )}
         </div>
     );
 }